@@ -0,0 +1,466 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::board::Board;
+use crate::coordinate::Coordinate;
+use crate::detonation::{self, RayStep};
+use crate::game::Game;
+use crate::i18n;
+use crate::item::Item;
+
+/// Enemigo que ninguna detonacion puede derrotar.
+///
+/// # Campos
+///
+/// * `coordinate`: Posicion del enemigo en el tablero.
+/// * `life`: Vida del enemigo.
+/// * `max_hits`: Mayor cantidad de rafagas que lo alcanzan detonando alguna de las bombas del tablero.
+#[derive(Debug, PartialEq)]
+pub struct UnbeatableEnemy {
+    pub coordinate: Coordinate,
+    pub life: u32,
+    pub max_hits: u32,
+}
+
+/// Observacion sobre los desvios de un tablero.
+///
+/// # Variantes
+///
+/// - `Loop(Vec<Coordinate>)`: Desvios que forman un ciclo cerrado, en el orden en que los recorre la rafaga.
+/// - `Unreachable(Coordinate)`: Desvio al que no llega la rafaga de ninguna bomba.
+/// - `IntoWall(Coordinate)`: Desvio que apunta directamente a una pared, por lo que corta la rafaga.
+#[derive(Debug, PartialEq)]
+pub enum DeflectionFinding {
+    Loop(Vec<Coordinate>),
+    Unreachable(Coordinate),
+    IntoWall(Coordinate),
+}
+
+impl std::fmt::Display for DeflectionFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeflectionFinding::Loop(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", i18n::DEFLECTION_LOOP.fill(&[&cycle.join(" -> ")]))
+            }
+            DeflectionFinding::Unreachable(coordinate) => {
+                write!(f, "{}", i18n::DEFLECTION_UNREACHABLE.fill(&[coordinate]))
+            }
+            DeflectionFinding::IntoWall(coordinate) => {
+                write!(f, "{}", i18n::DEFLECTION_INTO_WALL.fill(&[coordinate]))
+            }
+        }
+    }
+}
+
+/// Resultado del analisis de un tablero.
+///
+/// # Campos
+///
+/// * `unreachable`: Enemigos a los que no llega la rafaga de ninguna cadena de bombas.
+/// * `underpowered`: Enemigos alcanzables, pero con mas vida que la mayor cantidad de rafagas que pueden recibir.
+/// * `deflections`: Observaciones sobre los desvios. No impiden ganar el tablero.
+#[derive(Debug, PartialEq, Default)]
+pub struct CheckReport {
+    pub unreachable: Vec<UnbeatableEnemy>,
+    pub underpowered: Vec<UnbeatableEnemy>,
+    pub deflections: Vec<DeflectionFinding>,
+}
+
+impl CheckReport {
+    /// Indica si todos los enemigos del tablero pueden ser derrotados por alguna detonacion.
+    pub fn is_winnable(&self) -> bool {
+        self.unreachable.is_empty() && self.underpowered.is_empty()
+    }
+}
+
+impl std::fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_winnable() {
+            writeln!(f, "{}", i18n::CHECK_WINNABLE.text())?;
+        }
+        for enemy in &self.unreachable {
+            writeln!(f, "{}", i18n::CHECK_UNREACHABLE.fill(&[&enemy.coordinate]))?;
+        }
+        for enemy in &self.underpowered {
+            let args: [&dyn Display; 3] = [&enemy.coordinate, &enemy.life, &enemy.max_hits];
+            writeln!(f, "{}", i18n::CHECK_UNDERPOWERED.fill(&args))?;
+        }
+        for finding in &self.deflections {
+            writeln!(f, "{}", i18n::WARNING.fill(&[finding]))?;
+        }
+        Ok(())
+    }
+}
+
+/// Bomba cuya cadena de explosiones alcanza una casilla determinada.
+///
+/// # Campos
+///
+/// * `bomb`: Bomba que se detona para iniciar la cadena.
+/// * `hits`: Cantidad de rafagas que recibe la casilla.
+/// * `paths`: Recorrido de cada una de esas rafagas, desde `bomb` hasta la casilla.
+#[derive(Debug, PartialEq)]
+pub struct BlastRoute {
+    pub bomb: Coordinate,
+    pub hits: u32,
+    pub paths: Vec<Vec<RayStep>>,
+}
+
+impl BlastRoute {
+    /// Describe la ruta en texto, con un camino por linea. Los desvios en los que gira la rafaga
+    /// y las bombas que se detonan en cadena se indican junto a su coordenada.
+    pub fn describe(&self, board: &Board) -> String {
+        let mut description = i18n::ROUTE_HEADER.fill(&[&self.bomb, &self.hits]);
+        description.push('\n');
+        for path in &self.paths {
+            let steps: Vec<String> = path
+                .iter()
+                .enumerate()
+                .map(|(i, step)| describe_step(board, step, i == 0, i + 1 == path.len()))
+                .collect();
+            description.push_str(&format!("  {}\n", steps.join(" -> ")));
+        }
+        description
+    }
+}
+
+fn describe_step(board: &Board, step: &RayStep, first: bool, last: bool) -> String {
+    match board.item_at(&step.coordinate) {
+        Item::Deflection(direction) if !last => {
+            i18n::ROUTE_DEFLECTED.fill(&[&step.coordinate, &direction])
+        }
+        item @ (Item::NormalBomb(_) | Item::TransferBomb(_)) if !first => {
+            i18n::ROUTE_DETONATES.fill(&[&step.coordinate, &item])
+        }
+        _ => step.coordinate.to_string(),
+    }
+}
+
+/// Resultado de detonar una de las bombas del tablero.
+///
+/// # Campos
+///
+/// * `bomb`: Bomba que se detona para iniciar la cadena.
+/// * `defeated`: Cantidad de enemigos derrotados.
+/// * `damage`: Vida total que pierden los enemigos, derrotados o no.
+#[derive(Debug, PartialEq)]
+pub struct Solution {
+    pub bomb: Coordinate,
+    pub defeated: usize,
+    pub damage: u32,
+}
+
+/// Detona por separado cada bomba del tablero y devuelve el resultado de cada una, de mejor a
+/// peor: primero las que derrotan mas enemigos y, entre ellas, las que mas daño hacen. A igual
+/// resultado, las bombas quedan ordenadas por fila y columna.
+pub fn solve(game: &Game) -> Vec<Solution> {
+    let enemies = game.enemies();
+    let mut solutions = Vec::new();
+    for bomb in game.bombs() {
+        let (board, _) = match game.simulate(bomb.x, bomb.y) {
+            Ok(result) => result,
+            Err(_) => continue,
+        };
+        let mut solution = Solution {
+            bomb,
+            defeated: 0,
+            damage: 0,
+        };
+        for enemy in &enemies {
+            let before = life(game.board().item_at(enemy));
+            let after = life(board.item_at(enemy));
+            solution.damage += before - after;
+            if after == 0 {
+                solution.defeated += 1;
+            }
+        }
+        solutions.push(solution);
+    }
+    solutions.sort_by(|a, b| {
+        (b.defeated, b.damage)
+            .cmp(&(a.defeated, a.damage))
+            .then(a.bomb.cmp(&b.bomb))
+    });
+    solutions
+}
+
+/// Describe el resultado de `solve`: una linea por bomba, de mejor a peor, y una linea final que
+/// indica cual derrota a todos los enemigos o que ninguna lo hace. Devuelve el texto junto con
+/// `true` si alguna bomba derrota a todos los enemigos.
+pub fn describe_solutions(game: &Game) -> (String, bool) {
+    let enemies = game.enemies().len();
+    let solutions = solve(game);
+    let mut text = String::new();
+    for solution in &solutions {
+        let args: [&dyn Display; 4] = [
+            &solution.bomb,
+            &solution.defeated,
+            &enemies,
+            &solution.damage,
+        ];
+        text.push_str(&i18n::SOLVE_BOMB.fill(&args));
+        text.push('\n');
+    }
+    let winner = match solutions.first() {
+        Some(best) if best.defeated == enemies => {
+            text.push_str(&i18n::SOLVE_ALL.fill(&[&best.bomb]));
+            true
+        }
+        _ => {
+            text.push_str(i18n::SOLVE_NONE.text());
+            false
+        }
+    };
+    text.push('\n');
+    (text, winner)
+}
+
+fn life(item: Item) -> u32 {
+    match item {
+        Item::Enemy(life) => life,
+        _ => 0,
+    }
+}
+
+/// Busca las bombas cuya cadena de explosiones alcanza `target` y el recorrido que hace cada rafaga
+/// hasta llegar, incluyendo los giros en los desvios. Las bombas se devuelven ordenadas por fila y columna.
+pub fn bombs_reaching(game: &Game, target: &Coordinate) -> Vec<BlastRoute> {
+    let mut routes = Vec::new();
+    for bomb in game.bombs() {
+        let report = match detonation::detonate(game.board(), &bomb) {
+            Some(report) => report,
+            None => continue,
+        };
+        let hits = *report.affected.get(target).unwrap_or(&0);
+        if hits > 0 {
+            routes.push(BlastRoute {
+                bomb,
+                hits,
+                paths: report.paths_to(target),
+            });
+        }
+    }
+    routes
+}
+
+/// Analiza el tablero combinando el alcance de todas las bombas y detecta los enemigos que no
+/// pueden ser derrotados con ninguna detonacion.
+///
+/// Para cada enemigo se busca la detonacion inicial que mas rafagas le hace llegar. Si ninguna
+/// lo alcanza se lo informa como inalcanzable, y si su vida supera ese maximo, como imposible de derrotar.
+/// Tambien se incluyen las observaciones de `analyze_deflections`.
+pub fn check(game: &Game) -> CheckReport {
+    let max_hits = combined_reach(game);
+
+    let mut report = CheckReport::default();
+    for coordinate in game.enemies() {
+        let life = match game.get(coordinate.x, coordinate.y) {
+            Item::Enemy(life) => life,
+            _ => continue,
+        };
+        let hits = *max_hits.get(&coordinate).unwrap_or(&0);
+        let enemy = UnbeatableEnemy {
+            coordinate,
+            life,
+            max_hits: hits,
+        };
+        if hits == 0 {
+            report.unreachable.push(enemy);
+        } else if hits < life {
+            report.underpowered.push(enemy);
+        }
+    }
+    report.deflections = analyze_deflections(game);
+    report
+}
+
+/// Analiza estaticamente los desvios del tablero.
+///
+/// Detecta los ciclos cerrados de desvios, los desvios a los que no llega ninguna rafaga y los
+/// que apuntan directamente a una pared. Para buscar ciclos solo se consideran las paredes como
+/// obstaculo, ya que las bombas de traspaso atraviesan las rocas.
+pub fn analyze_deflections(game: &Game) -> Vec<DeflectionFinding> {
+    let deflections = game.deflections();
+    let reach = combined_reach(game);
+    let mut findings = Vec::new();
+
+    let mut next: HashMap<Coordinate, Coordinate> = HashMap::new();
+    for deflection in &deflections {
+        let direction = match game.get(deflection.x, deflection.y) {
+            Item::Deflection(direction) => direction,
+            _ => continue,
+        };
+        if let Some(target) = next_deflection(game, deflection, direction) {
+            next.insert(*deflection, target);
+        }
+    }
+    findings.extend(find_loops(&deflections, &next));
+
+    for deflection in &deflections {
+        if !reach.contains_key(deflection) {
+            findings.push(DeflectionFinding::Unreachable(*deflection));
+        }
+    }
+    for deflection in &deflections {
+        if let Item::Deflection(direction) = game.get(deflection.x, deflection.y) {
+            let ahead = deflection.towards(direction);
+            if let Some(Item::Wall) = ahead.map(|c| game.get(c.x, c.y)) {
+                findings.push(DeflectionFinding::IntoWall(*deflection));
+            }
+        }
+    }
+    findings
+}
+
+/// Combina el alcance de todas las bombas del tablero: para cada casilla guarda la mayor cantidad
+/// de rafagas que recibe al detonar alguna de ellas.
+fn combined_reach(game: &Game) -> HashMap<Coordinate, u32> {
+    let mut max_hits: HashMap<Coordinate, u32> = HashMap::new();
+    for bomb in game.bombs() {
+        let affected = game.blast_reach(&bomb).unwrap_or_default();
+        for (coordinate, hits) in affected {
+            let best = max_hits.entry(coordinate).or_insert(0);
+            *best = (*best).max(hits);
+        }
+    }
+    max_hits
+}
+
+/// Sigue la rafaga que sale de `start` en `direction` y devuelve el primer desvio que encuentra,
+/// o `None` si antes choca con una pared o sale del tablero.
+fn next_deflection(game: &Game, start: &Coordinate, direction: char) -> Option<Coordinate> {
+    let mut current = start.towards(direction)?;
+    loop {
+        match game.get(current.x, current.y) {
+            Item::Wall => return None,
+            Item::Deflection(_) => return Some(current),
+            _ => current = current.towards(direction)?,
+        }
+    }
+}
+
+/// Busca los ciclos del grafo donde cada desvio apunta al siguiente desvio que alcanza su rafaga.
+/// Cada ciclo se informa una sola vez, empezando por su desvio de menor coordenada.
+fn find_loops(
+    deflections: &[Coordinate],
+    next: &HashMap<Coordinate, Coordinate>,
+) -> Vec<DeflectionFinding> {
+    let mut loops = Vec::new();
+    let mut visited: HashSet<Coordinate> = HashSet::new();
+
+    for start in deflections {
+        let mut path: Vec<Coordinate> = Vec::new();
+        let mut current = Some(*start);
+        while let Some(coordinate) = current {
+            if visited.contains(&coordinate) {
+                if let Some(position) = path.iter().position(|c| *c == coordinate) {
+                    let mut cycle = path[position..].to_vec();
+                    let min = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap_or(0);
+                    cycle.rotate_left(min);
+                    loops.push(DeflectionFinding::Loop(cycle));
+                }
+                break;
+            }
+            visited.insert(coordinate);
+            path.push(coordinate);
+            current = next.get(&coordinate).copied();
+        }
+    }
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_winnable_board() {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        let report = check(&game);
+        assert!(report.is_winnable());
+    }
+
+    #[test]
+    fn test_check_unreachable_enemy() {
+        let game = Game::from_file("./tests/inputs/unreachable_enemy.txt").unwrap();
+        let report = check(&game);
+        assert_eq!(
+            report.unreachable,
+            vec![UnbeatableEnemy {
+                coordinate: Coordinate::new(2, 2, 2),
+                life: 1,
+                max_hits: 0,
+            }]
+        );
+        assert_eq!(
+            report.underpowered,
+            vec![UnbeatableEnemy {
+                coordinate: Coordinate::new(0, 2, 2),
+                life: 3,
+                max_hits: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_deflection_loop() {
+        let game = Game::from_file("./tests/inputs/deflection_loop.txt").unwrap();
+        let findings = analyze_deflections(&game);
+        assert!(findings.contains(&DeflectionFinding::Loop(vec![
+            Coordinate::new(0, 1, 3),
+            Coordinate::new(0, 3, 3),
+            Coordinate::new(2, 3, 3),
+            Coordinate::new(2, 1, 3),
+        ])));
+    }
+
+    #[test]
+    fn test_deflection_unreachable_and_into_wall() {
+        let game = Game::from_file("./tests/inputs/deflection_into_wall.txt").unwrap();
+        let findings = analyze_deflections(&game);
+        assert_eq!(
+            findings,
+            vec![
+                DeflectionFinding::Unreachable(Coordinate::new(2, 2, 2)),
+                DeflectionFinding::IntoWall(Coordinate::new(0, 2, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_ranks_bombs() {
+        let game = Game::from_text("B1 F1 _\n_ _ _\nB1 F2 B1\n").unwrap();
+        let solutions = solve(&game);
+        assert_eq!(solutions.len(), 3);
+        assert_eq!(solutions[0].bomb, game.board().coordinate(0, 0));
+        assert_eq!((solutions[0].defeated, solutions[0].damage), (1, 1));
+        assert_eq!(solutions[1].bomb, game.board().coordinate(2, 0));
+        assert_eq!((solutions[1].defeated, solutions[1].damage), (0, 1));
+        assert_eq!(solutions[2].bomb, game.board().coordinate(2, 2));
+    }
+
+    #[test]
+    fn test_bombs_reaching_enemy() {
+        let game = Game::from_file("./tests/inputs/catedra_3.txt").unwrap();
+        let target = game.board().coordinate(2, 4);
+        let routes = bombs_reaching(&game, &target);
+
+        let bombs: Vec<Coordinate> = routes.iter().map(|route| route.bomb).collect();
+        assert_eq!(
+            bombs,
+            vec![
+                game.board().coordinate(2, 0),
+                game.board().coordinate(4, 0),
+                game.board().coordinate(4, 2),
+            ]
+        );
+        assert_eq!(routes[1].hits, 2);
+        assert_eq!(
+            routes[2].describe(game.board()),
+            "Bomba en (2, 4): 2 impacto(s)\n\
+             \x20 (2, 4) -> (3, 4) -> (4, 4) desvio a U -> (4, 3) -> (4, 2)\n\
+             \x20 (2, 4) -> (1, 4) -> (0, 4) detona B2 -> (0, 3) -> (0, 2) detona S4 -> (1, 2) -> (2, 2) -> (3, 2) -> (4, 2)\n"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::config;
+use crate::error::BombermanError;
+use crate::file_io;
+use crate::game::Game;
+use crate::header::Rule;
+use crate::i18n;
+use crate::suite::{self, Outcome};
+use crate::xml;
+
+/// Nombre del conjunto de pruebas en el reporte JUnit.
+const SUITE_NAME: &str = "bomberman";
+
+/// Linea del manifiesto de un lote de tableros.
+///
+/// Cada linea del manifiesto tiene la forma `tablero.txt x y [esperado.txt]`, con la coordenada
+/// en el mismo orden que los argumentos de linea de comandos (primero la columna y despues la
+/// fila). Las rutas son relativas a la carpeta del lote. Las lineas vacias y las que empiezan
+/// con `#` se ignoran.
+///
+/// # Campos
+///
+/// * `line`: Linea del manifiesto, empezando en 1.
+/// * `input`: Ruta del tablero.
+/// * `bomb`: Fila y columna de la bomba a detonar.
+/// * `expected`: Ruta del archivo con la salida esperada, si la hay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub line: usize,
+    pub input: String,
+    pub bomb: (usize, usize),
+    pub expected: Option<String>,
+}
+
+impl Entry {
+    /// Nombre del caso en los reportes: el tablero y la coordenada tal como aparecen en el manifiesto.
+    pub fn name(&self) -> String {
+        format!("{} ({}, {})", self.input, self.bomb.1, self.bomb.0)
+    }
+}
+
+/// Resultado de ejecutar una linea del manifiesto.
+///
+/// # Campos
+///
+/// * `entry`: La linea ejecutada.
+/// * `output`: El tablero final o el error de la ejecucion.
+/// * `expected`: El contenido del archivo con la salida esperada, o el error al leerlo.
+#[derive(Debug, PartialEq)]
+pub struct EntryResult {
+    pub entry: Entry,
+    pub output: Result<String, BombermanError>,
+    pub expected: Option<Result<String, BombermanError>>,
+}
+
+impl EntryResult {
+    /// Devuelve la salida tal como la escribiria una ejecucion normal: el tablero final o `Error: ...`.
+    pub fn rendered_output(&self) -> String {
+        match &self.output {
+            Ok(board) => board.clone(),
+            Err(e) => format!("Error: {}\n", e.message()),
+        }
+    }
+
+    /// Compara la salida con la esperada, con el mismo criterio que `suite::CaseResult::outcome`.
+    /// Si no se pudo leer la salida esperada, el resultado es `Outcome::Error`.
+    pub fn outcome(&self) -> Outcome {
+        match (&self.expected, &self.output) {
+            (Some(Ok(expected)), _) if suite::same_output(expected, &self.rendered_output()) => {
+                Outcome::Passed
+            }
+            (Some(Ok(_)), _) => Outcome::Failed,
+            (Some(Err(_)), _) | (None, Err(_)) => Outcome::Error,
+            (None, Ok(_)) => Outcome::Unchecked,
+        }
+    }
+}
+
+/// Lee el texto de un manifiesto.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidManifest` con la linea correspondiente si alguna linea no
+/// tiene tres o cuatro campos o si su coordenada no son dos enteros no negativos.
+pub fn parse_manifest(content: &str) -> Result<Vec<Entry>, BombermanError> {
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() || file_io::is_comment(line) {
+            continue;
+        }
+        let invalid = BombermanError::InvalidManifest { line: i + 1 };
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (input, y, x, expected) = match fields[..] {
+            [input, y, x] => (input, y, x, None),
+            [input, y, x, expected] => (input, y, x, Some(expected.to_string())),
+            _ => return Err(invalid),
+        };
+        let bomb = match (config::get_coordinate(x), config::get_coordinate(y)) {
+            (Ok(x), Ok(y)) => (x, y),
+            _ => return Err(invalid),
+        };
+        entries.push(Entry {
+            line: i + 1,
+            input: input.to_string(),
+            bomb,
+            expected,
+        });
+    }
+    Ok(entries)
+}
+
+/// Ejecuta una linea del manifiesto: lee su tablero, le agrega las reglas `rules`, detona la
+/// bomba indicada y lee la salida esperada, si la hay. Las rutas se toman relativas a `dir`.
+pub fn run_entry(dir: &Path, entry: Entry, rules: &[Rule]) -> EntryResult {
+    let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+    let output = Game::from_file(&path(&entry.input)).and_then(|game| {
+        let game = game.with_rules(rules);
+        let (x, y) = entry.bomb;
+        let (x, y) = config::cell(x, y)?;
+        let (board, _) = game.simulate(x, y)?;
+        Ok(board.to_string())
+    });
+    let expected = entry
+        .expected
+        .as_deref()
+        .map(|name| file_io::read_to_string(&path(name)));
+    EntryResult {
+        entry,
+        output,
+        expected,
+    }
+}
+
+/// Ejecuta todas las lineas del manifiesto repartiendolas entre `workers` hilos. Los resultados
+/// se devuelven en el mismo orden que el manifiesto.
+pub fn run_batch(
+    dir: &Path,
+    entries: Vec<Entry>,
+    workers: usize,
+    rules: &[Rule],
+) -> Vec<EntryResult> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<EntryResult>>> =
+        Mutex::new(entries.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, entries.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let entry = match entries.get(i) {
+                    Some(entry) => entry.clone(),
+                    None => break,
+                };
+                let result = run_entry(dir, entry, rules);
+                if let Ok(mut results) = results.lock() {
+                    results[i] = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Arma el reporte del lote en el formato XML de JUnit: un `testcase` por linea del manifiesto,
+/// con un elemento `failure` si la salida no coincide con la esperada o `error` si la ejecucion
+/// fallo sin tener salida esperada (o no se pudo leer la esperada).
+pub fn junit(results: &[EntryResult]) -> String {
+    let count = |outcome: Outcome| results.iter().filter(|r| r.outcome() == outcome).count();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\">\n",
+        SUITE_NAME,
+        results.len(),
+        count(Outcome::Failed),
+        count(Outcome::Error)
+    ));
+    for result in results {
+        xml.push_str(&format!(
+            "  <testcase classname=\"{}\" name=\"{}\"",
+            SUITE_NAME,
+            xml::escape(&result.entry.name())
+        ));
+        let element = match (result.outcome(), &result.expected, &result.output) {
+            (Outcome::Failed, Some(Ok(expected)), _) => Some(format!(
+                "<failure message=\"{}\">{}===\n{}</failure>",
+                xml::escape(i18n::BATCH_MISMATCH.text()),
+                xml::escape(&result.rendered_output()),
+                xml::escape(expected)
+            )),
+            (Outcome::Error, Some(Err(e)), _) | (Outcome::Error, None, Err(e)) => Some(format!(
+                "<error message=\"{}\"/>",
+                xml::escape(&e.message())
+            )),
+            _ => None,
+        };
+        match element {
+            Some(element) => xml.push_str(&format!(">\n    {}\n  </testcase>\n", element)),
+            None => xml.push_str("/>\n"),
+        }
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let entries = parse_manifest("# casos\n\ncatedra_1.txt 0 0 out.txt\nx.txt 2 4\n").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 3);
+        assert_eq!(entries[0].expected.as_deref(), Some("out.txt"));
+        assert_eq!(entries[1].bomb, (4, 2));
+        assert_eq!(entries[1].name(), "x.txt (2, 4)");
+        assert_eq!(
+            parse_manifest("a.txt 1\n"),
+            Err(BombermanError::InvalidManifest { line: 1 })
+        );
+    }
+
+    #[test]
+    fn test_run_batch_keeps_manifest_order() {
+        let entries = parse_manifest(
+            "catedra_1.txt 0 0 ../expected_output/catedra_1.txt\n\
+             catedra_2.txt 2 4 ../expected_output/catedra_1.txt\n\
+             no_existe.txt 0 0\n\
+             catedra_3.txt 0 4\n",
+        )
+        .unwrap();
+        let results = run_batch(Path::new("./tests/inputs"), entries, 3, &[]);
+        let outcomes: Vec<Outcome> = results.iter().map(EntryResult::outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Passed,
+                Outcome::Failed,
+                Outcome::Error,
+                Outcome::Unchecked
+            ]
+        );
+
+        let xml = junit(&results);
+        assert!(xml.contains("tests=\"4\" failures=\"1\" errors=\"1\""));
+        assert!(xml.contains("<testcase classname=\"bomberman\" name=\"catedra_1.txt (0, 0)\"/>"));
+        assert!(xml.contains("<error message=\"El archivo input no existe\"/>"));
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::coordinate::Coordinate;
+use crate::item::Item;
+
+/// Tablero cuadrado del juego.
+///
+/// Guarda solamente las casillas ocupadas; cualquier coordenada que no este en el mapa se
+/// considera vacia (`Item::Empty`).
+///
+/// # Campos
+///
+/// * `map`: Items del tablero indexados por coordenada.
+/// * `dimension`: Cantidad de filas (y de columnas) del tablero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Board {
+    map: HashMap<Coordinate, Item>,
+    dimension: u32,
+}
+
+impl Board {
+    /// Crea un tablero a partir de los items leidos y su dimension.
+    pub fn new(map: HashMap<Coordinate, Item>, dimension: u32) -> Board {
+        Board { map, dimension }
+    }
+
+    /// Devuelve la dimension (cantidad de filas y columnas) del tablero.
+    pub fn dimension(&self) -> u32 {
+        self.dimension
+    }
+
+    /// Construye la coordenada de la fila `x` y columna `y` con el maximo correspondiente a este tablero.
+    pub fn coordinate(&self, x: u32, y: u32) -> Coordinate {
+        Coordinate::new(x, y, self.dimension.saturating_sub(1))
+    }
+
+    /// Devuelve el item que se encuentra en la fila `x` y columna `y`, o `Item::Empty` si la casilla esta vacia.
+    pub fn get(&self, x: u32, y: u32) -> Item {
+        self.item_at(&self.coordinate(x, y))
+    }
+
+    /// Devuelve el item que se encuentra en `coordinate`, o `Item::Empty` si la casilla esta vacia.
+    pub fn item_at(&self, coordinate: &Coordinate) -> Item {
+        *self.map.get(coordinate).unwrap_or(&Item::Empty)
+    }
+
+    /// Coloca `item` en `coordinate`. Colocar `Item::Empty` libera la casilla.
+    pub fn set(&mut self, coordinate: Coordinate, item: Item) {
+        if item == Item::Empty {
+            self.map.remove(&coordinate);
+        } else {
+            self.map.insert(coordinate, item);
+        }
+    }
+
+    /// Devuelve las coordenadas de las casillas cuyo item cumple `predicate`, ordenadas por fila y columna.
+    pub fn coordinates_where(&self, predicate: fn(&Item) -> bool) -> Vec<Coordinate> {
+        let mut coordinates: Vec<Coordinate> = self
+            .map
+            .iter()
+            .filter(|(_, item)| predicate(item))
+            .map(|(coordinate, _)| *coordinate)
+            .collect();
+        coordinates.sort();
+        coordinates
+    }
+}
+
+/// Muestra el tablero con el mismo formato que el archivo de entrada: una fila por linea y las
+/// casillas separadas por un espacio.
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for x in 0..self.dimension {
+            for y in 0..self.dimension {
+                if y != 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", self.get(x, y))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_empty_frees_square() {
+        let mut board = Board::new(HashMap::new(), 2);
+        let coordinate = board.coordinate(1, 0);
+        board.set(coordinate, Item::Enemy(2));
+        assert_eq!(board.get(1, 0), Item::Enemy(2));
+        board.set(coordinate, Item::Empty);
+        assert_eq!(board, Board::new(HashMap::new(), 2));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut board = Board::new(HashMap::new(), 2);
+        board.set(board.coordinate(0, 0), Item::NormalBomb(1));
+        board.set(board.coordinate(1, 1), Item::Wall);
+        assert_eq!(board.to_string(), "B1 _\n_ W\n");
+    }
+}
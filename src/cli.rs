@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use crate::error::BombermanError;
+use crate::i18n::{self, Message};
+use crate::settings::Settings;
+
+/// Nombre del programa en los mensajes de uso.
+pub const PROGRAM: &str = "bomberman";
+/// Opcion que muestra la ayuda de un comando.
+pub const HELP_FLAG: &str = "--help";
+const SHORT_HELP_FLAG: &str = "-h";
+
+/// Opcion con nombre de un comando.
+///
+/// # Campos
+///
+/// * `name`: Nombre de la opcion, incluyendo los guiones (`--format`).
+/// * `value`: Nombre del valor que recibe la opcion (`formato`), o `None` si es un interruptor
+///   que no recibe valor.
+/// * `help`: Descripcion de la opcion para la ayuda, en cada idioma.
+#[derive(Debug)]
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    pub help: Message,
+}
+
+/// Resultado de un comando que se pudo ejecutar, que determina el codigo de salida del programa.
+///
+/// # Variantes
+///
+/// - `Success`: El resultado es positivo (codigo 0).
+/// - `Failure`: El resultado es negativo, por ejemplo un tablero que no se puede ganar (codigo 1).
+/// - `Invalid`: Errores encontrados en la entrada, que se informan en la salida de errores; el
+///   codigo es el de la categoria del primero.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Success,
+    Failure,
+    Invalid(Vec<BombermanError>),
+}
+
+/// Comando de la linea de comandos.
+///
+/// Los comandos se registran en una tabla; para agregar uno nuevo alcanza con declarar sus
+/// opciones y la funcion que lo ejecuta.
+///
+/// # Campos
+///
+/// * `name`: Nombre del comando (`run`, `check`, ...).
+/// * `args`: Argumentos posicionales, tal como se muestran en la ayuda.
+/// * `about`: Descripcion corta del comando, en cada idioma.
+/// * `flags`: Opciones con nombre que acepta el comando, ademas de `--help`.
+/// * `run`: Funcion que ejecuta el comando con los argumentos ya leidos y la configuracion del
+///   proyecto (`bomberman.toml`), y devuelve su resultado para que `main` termine el programa.
+pub struct Command {
+    pub name: &'static str,
+    pub args: &'static str,
+    pub about: Message,
+    pub flags: &'static [Flag],
+    pub run: fn(&Matches, &Settings) -> Result<Status, BombermanError>,
+}
+
+impl Command {
+    /// Devuelve la linea de uso del comando.
+    pub fn usage(&self) -> String {
+        let mut usage = format!("{} {}", PROGRAM, self.name);
+        if !self.args.is_empty() {
+            usage.push_str(&format!(" {}", self.args));
+        }
+        if !self.flags.is_empty() {
+            usage.push(' ');
+            usage.push_str(i18n::HELP_OPTIONS_PLACEHOLDER.text());
+        }
+        usage
+    }
+
+    /// Arma la ayuda del comando: su uso, su descripcion y sus opciones.
+    pub fn help(&self) -> String {
+        let mut help = format!(
+            "{}\n\n{}\n\n{}\n",
+            i18n::USAGE.fill(&[&self.usage()]),
+            self.about.text(),
+            i18n::HELP_OPTIONS.text()
+        );
+        let options: Vec<(String, &str)> = self
+            .flags
+            .iter()
+            .map(|flag| match flag.value {
+                Some(value) => (format!("{} <{}>", flag.name, value), flag.help.text()),
+                None => (flag.name.to_string(), flag.help.text()),
+            })
+            .chain([(HELP_FLAG.to_string(), i18n::HELP_FLAG.text())])
+            .collect();
+        let width = options
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+        for (name, text) in options {
+            help.push_str(&format!("  {:<width$}  {}\n", name, text));
+        }
+        help
+    }
+
+    /// Lee los argumentos del comando (sin el nombre del programa ni el del comando). Ver `parse`.
+    pub fn parse(&self, args: &[String]) -> Result<Matches, BombermanError> {
+        parse(self.flags, args)
+    }
+}
+
+/// Argumentos de un comando ya separados en posicionales y opciones.
+///
+/// # Campos
+///
+/// * `positional`: Argumentos posicionales, en orden.
+/// * `flags`: Opciones indicadas, con su valor si lo tienen. Si una opcion se repite, vale la ultima.
+/// * `help`: Si es `true` se pidio la ayuda del comando (`--help` o `-h`).
+#[derive(Debug, Default, PartialEq)]
+pub struct Matches {
+    pub positional: Vec<String>,
+    pub flags: HashMap<&'static str, Option<String>>,
+    pub help: bool,
+}
+
+impl Matches {
+    /// Devuelve el valor de la opcion `name`, si se indico.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.flags.get(name).and_then(|value| value.as_deref())
+    }
+
+    /// Indica si se uso la opcion `name`.
+    pub fn is_set(&self, name: &str) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// Devuelve los argumentos posicionales si su cantidad es alguna de `counts`.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InsufficientInput` si la cantidad no es ninguna de las esperadas.
+    pub fn expect_positional(&self, counts: &[usize]) -> Result<&[String], BombermanError> {
+        if counts.contains(&self.positional.len()) {
+            Ok(&self.positional)
+        } else {
+            Err(BombermanError::InsufficientInput)
+        }
+    }
+}
+
+/// Lee argumentos de linea de comandos que aceptan las opciones `flags`.
+///
+/// Las opciones pueden aparecer en cualquier posicion, como `--nombre valor` o `--nombre=valor`.
+/// El resto de los argumentos se consideran posicionales.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::UnknownFlag` si alguna opcion no esta en `flags` y
+/// `BombermanError::MissingFlagValue` si a una opcion le falta su valor.
+pub fn parse(flags: &'static [Flag], args: &[String]) -> Result<Matches, BombermanError> {
+    let mut matches = Matches::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == HELP_FLAG || arg == SHORT_HELP_FLAG {
+            matches.help = true;
+            continue;
+        }
+        if !arg.starts_with("--") {
+            matches.positional.push(arg.clone());
+            continue;
+        }
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let flag = flags
+            .iter()
+            .find(|flag| flag.name == name)
+            .ok_or_else(|| BombermanError::UnknownFlag(name.to_string()))?;
+        let value = match (flag.value, inline) {
+            (Some(_), Some(value)) => Some(value),
+            (Some(_), None) => Some(
+                iter.next()
+                    .cloned()
+                    .ok_or_else(|| BombermanError::MissingFlagValue(name.to_string()))?,
+            ),
+            (None, Some(_)) => return Err(BombermanError::UnknownFlag(arg.clone())),
+            (None, None) => None,
+        };
+        matches.flags.insert(flag.name, value);
+    }
+    Ok(matches)
+}
+
+/// Busca el comando `name` en la tabla de comandos.
+pub fn find<'a>(commands: &'a [Command], name: &str) -> Option<&'a Command> {
+    commands.iter().find(|command| command.name == name)
+}
+
+/// Quita de `args` la opcion global `name`, que vale para todos los comandos, y devuelve su
+/// valor si se indico. Acepta las formas `--nombre valor` y `--nombre=valor`.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::MissingFlagValue` si a la opcion le falta su valor.
+pub fn take_global(args: &mut Vec<String>, name: &str) -> Result<Option<String>, BombermanError> {
+    let mut value = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == name {
+            if i + 1 >= args.len() {
+                return Err(BombermanError::MissingFlagValue(name.to_string()));
+            }
+            value = Some(args.remove(i + 1));
+            args.remove(i);
+        } else if let Some(inline) = args[i].strip_prefix(name).and_then(|v| v.strip_prefix('=')) {
+            value = Some(inline.to_string());
+            args.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    Ok(value)
+}
+
+/// Arma la ayuda general del programa con la lista de comandos y las opciones globales.
+pub fn overview(commands: &[Command], globals: &[Flag]) -> String {
+    let mut help = format!(
+        "{}\n\n{}\n",
+        i18n::HELP_OVERVIEW.fill(&[&PROGRAM]),
+        i18n::HELP_COMMANDS.text()
+    );
+    let width = commands.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for command in commands {
+        help.push_str(&format!(
+            "  {:<width$}  {}\n",
+            command.name,
+            command.about.text()
+        ));
+    }
+    if !globals.is_empty() {
+        help.push_str(&format!("\n{}\n", i18n::HELP_GLOBAL_OPTIONS.text()));
+        for flag in globals {
+            let name = match flag.value {
+                Some(value) => format!("{} <{}>", flag.name, value),
+                None => flag.name.to_string(),
+            };
+            help.push_str(&format!("  {}  {}\n", name, flag.help.text()));
+        }
+    }
+    help.push('\n');
+    help.push_str(&i18n::HELP_HINT.fill(&[&PROGRAM, &HELP_FLAG]));
+    help.push('\n');
+    help
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAGS: &[Flag] = &[
+        Flag {
+            name: "--format",
+            value: Some("formato"),
+            help: Message {
+                es: "Formato de salida",
+                en: "Output format",
+            },
+        },
+        Flag {
+            name: "--show",
+            value: None,
+            help: Message {
+                es: "Muestra el tablero",
+                en: "Shows the board",
+            },
+        },
+    ];
+
+    fn command() -> Command {
+        Command {
+            name: "run",
+            args: "tablero.txt",
+            about: Message {
+                es: "Ejecuta",
+                en: "Runs",
+            },
+            flags: FLAGS,
+            run: |_, _| Ok(Status::Success),
+        }
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_flags_and_positional() {
+        let matches = command()
+            .parse(&args(&[
+                "a.txt",
+                "--format",
+                "rle",
+                "--show",
+                "-",
+                "--format=json",
+            ]))
+            .unwrap();
+        assert_eq!(matches.positional, args(&["a.txt", "-"]));
+        assert_eq!(matches.value("--format"), Some("json"));
+        assert!(matches.is_set("--show"));
+        assert!(!matches.help);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            command().parse(&args(&["--colour"])),
+            Err(BombermanError::UnknownFlag("--colour".to_string()))
+        );
+        assert_eq!(
+            command().parse(&args(&["--format"])),
+            Err(BombermanError::MissingFlagValue("--format".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_take_global() {
+        let mut values = args(&["run", "--error-format", "json", "a.txt"]);
+        assert_eq!(
+            take_global(&mut values, "--error-format"),
+            Ok(Some("json".to_string()))
+        );
+        assert_eq!(values, args(&["run", "a.txt"]));
+        let mut values = args(&["--error-format=text", "a.txt"]);
+        assert_eq!(
+            take_global(&mut values, "--error-format"),
+            Ok(Some("text".to_string()))
+        );
+        assert_eq!(values, args(&["a.txt"]));
+    }
+
+    #[test]
+    fn test_help() {
+        assert!(command().parse(&args(&["-h"])).unwrap().help);
+        let help = command().help();
+        assert!(help.starts_with("Uso: bomberman run tablero.txt [opciones]\n"));
+        assert!(help.contains("  --format <formato>  Formato de salida\n"));
+        assert!(help.contains("  --help              Muestra esta ayuda\n"));
+    }
+}
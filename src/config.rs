@@ -1,9 +1,102 @@
 // use constants::*;
 
-use std::env;
+use std::path::Path;
 
+use crate::cli::{self, Flag, Matches};
 use crate::constants::*;
 use crate::error::BombermanError;
+use crate::file_io;
+use crate::format::{Format, LineFormat};
+use crate::game::Game;
+use crate::header::Rule;
+use crate::i18n::Message;
+use crate::settings::{CoordinateOrder, Settings};
+
+const INPUT_FLAG: &str = "--input";
+const OUTPUT_FLAG: &str = "--output";
+const COLUMN_FLAG: &str = "--column";
+const ROW_FLAG: &str = "--row";
+const FORMAT_FLAG: &str = "--format";
+const STRICT_FLAG: &str = "--strict";
+const SHOW_FLAG: &str = "--show";
+const FORCE_FLAG: &str = "--force";
+const TRACE_FLAG: &str = "--trace";
+
+/// Opciones que acepta la ejecucion de una detonacion (`run`).
+pub const FLAGS: &[Flag] = &[
+    Flag {
+        name: INPUT_FLAG,
+        value: Some("ruta"),
+        help: Message {
+            es: "Archivo con el tablero, o - para la entrada estandar",
+            en: "File with the board, or - for standard input",
+        },
+    },
+    Flag {
+        name: OUTPUT_FLAG,
+        value: Some("ruta"),
+        help: Message {
+            es: "Carpeta donde se guarda el tablero final, o - para la salida estandar",
+            en: "Directory where the final board is saved, or - for standard output",
+        },
+    },
+    Flag {
+        name: COLUMN_FLAG,
+        value: Some("x"),
+        help: Message {
+            es: "Columna de la bomba a detonar",
+            en: "Column of the bomb to detonate",
+        },
+    },
+    Flag {
+        name: ROW_FLAG,
+        value: Some("y"),
+        help: Message {
+            es: "Fila de la bomba a detonar",
+            en: "Row of the bomb to detonate",
+        },
+    },
+    Flag {
+        name: FORMAT_FLAG,
+        value: Some("formato"),
+        help: Message {
+            es: "Formato de entrada y salida: plain, rle o json",
+            en: "Input and output format: plain, rle or json",
+        },
+    },
+    Flag {
+        name: STRICT_FLAG,
+        value: None,
+        help: Message {
+            es: "Lee el tablero tal cual, sin normalizar fines de linea ni tabulaciones",
+            en: "Reads the board as is, without normalizing line endings or tabs",
+        },
+    },
+    Flag {
+        name: SHOW_FLAG,
+        value: None,
+        help: Message {
+            es: "Muestra en la terminal el tablero inicial y el final",
+            en: "Shows the initial and final board in the terminal",
+        },
+    },
+    Flag {
+        name: FORCE_FLAG,
+        value: None,
+        help: Message {
+            es: "Sobrescribe el archivo de salida si ya existe",
+            en: "Overwrites the output file if it already exists",
+        },
+    },
+    Flag {
+        name: TRACE_FLAG,
+        value: Some("text|json"),
+        help: Message {
+            es: "Muestra cada evento de la propagacion de la detonacion, en texto o en JSON lines",
+            en: "Shows every event of the blast propagation, as text or as JSON lines",
+        },
+    },
+];
 
 /// Configuración para la ejecución del programa Bomberman.
 ///
@@ -13,53 +106,226 @@ use crate::error::BombermanError;
 ///
 /// * `name_input`: Nombre del archivo de entrada que contiene el mapa del juego.
 /// * `path_output`: Ruta de la carpeta/directorio donde se guardarán los resultados del juego.
-/// * `x`: Coordenada X de la primera bomba a detonar.
-/// * `y`: Coordenada Y de la primera bomba a detonar.
+/// * `x`: Coordenada X de la primera bomba a detonar, o `None` para usar la del encabezado del tablero.
+/// * `y`: Coordenada Y de la primera bomba a detonar, o `None` para usar la del encabezado del tablero.
+/// * `format`: Formato del archivo de entrada y del de salida (`--format plain|rle|json`).
+/// * `strict`: Si es `true` (`--strict`), el archivo de entrada se lee tal cual, sin normalizar
+///   fines de linea, tabulaciones ni lineas vacias al final.
+/// * `show`: Si es `true` (`--show`), se muestran en la terminal el tablero inicial y el final.
+/// * `force`: Si es `true` (`--force`), se sobrescribe el archivo de salida si ya existe.
+/// * `trace`: Formato del registro de la propagacion (`--trace text|json`), o `None` si no se pidio.
+/// * `rules`: Reglas que se agregan a las del encabezado del tablero (`rules` en `bomberman.toml`).
 ///
 /// Esta estructura encapsula la configuración necesaria para la ejecución de Bomberman, incluyendo
 /// el nombre del archivo de entrada, la ruta de la carpeta de salida y las coordenadas de la
 /// primera bomba a detonar.
 ///
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Config {
     pub name_input: String,
     pub path_output: String,
-    pub x: usize,
-    pub y: usize,
+    pub x: Option<usize>,
+    pub y: Option<usize>,
+    pub format: Format,
+    pub strict: bool,
+    pub show: bool,
+    pub force: bool,
+    pub trace: Option<LineFormat>,
+    pub rules: Vec<Rule>,
 }
 
 impl Config {
-    /// Crea una nueva instancia de `Config` a partir de los argumentos de línea de comandos.
+    /// Crea una nueva instancia de `Config` combinando, en orden de prioridad, los argumentos ya
+    /// leidos de `run`, la configuracion del proyecto (`bomberman.toml`) y los valores por defecto.
+    ///
+    /// Las opciones `--output` y `--format` forman una capa de configuracion que se combina con
+    /// `settings` (ver `Settings::over`); la configuracion resultante decide la carpeta de salida
+    /// por defecto, el formato, el orden de las coordenadas y las reglas.
     ///
-    /// Esta función toma los argumentos de línea de comandos proporcionados por el usuario y crea una
-    /// nueva instancia de `Config` que contiene la información necesaria para configurar la ejecución
-    /// del juego Bomberman.
+    /// El archivo de entrada, la carpeta de salida, la columna y la fila se pueden indicar con
+    /// sus opciones (`--input`, `--output`, `--column`, `--row`) o como argumentos posicionales,
+    /// en ese orden (las coordenadas, en el orden de `settings.coordinates`). Los posicionales
+    /// completan, en orden, los valores que no se indicaron con una opcion. La coordenada puede
+    /// omitirse si el tablero la indica en su encabezado, y la salida si la configuracion tiene
+    /// una carpeta de salida.
+    ///
+    /// Cuando la salida se puede omitir, los posicionales son ambiguos: `t.txt 4 2` puede ser la
+    /// entrada, la salida `4` y una sola coordenada, o la entrada y las dos coordenadas. Primero
+    /// se toma el segundo posicional como salida; solo si asi queda una unica coordenada se
+    /// vuelven a repartir sin la salida, que se toma de la configuracion.
     ///
     /// # Argumentos
     ///
-    /// * `args`: Un vector de cadenas que contiene los argumentos de línea de comandos. Debe tener
-    ///   exactamente 5 elementos, incluyendo el nombre del programa.
+    /// * `settings`: La configuracion del proyecto, que completa lo que no indican los argumentos.
+    /// * `matches`: Los argumentos leidos con las opciones de `FLAGS`.
     ///
     /// # Errores
     ///
-    /// Esta función devuelve un resultado que indica si la creación de la configuración fue exitosa o
-    /// si hubo errores en los argumentos de entrada.
+    /// Devuelve `BombermanError::InsufficientInput` si falta la entrada, la salida o una sola de
+    /// las coordenadas, o si sobran argumentos, `BombermanError::InvalidCoordinate` si alguna
+    /// coordenada no es un entero no negativo y `BombermanError::InvalidFormat` si el formato
+    /// pedido (o el del registro) no existe.
     ///
-    pub fn new() -> Result<Config, BombermanError> {
-        let args: Vec<String> = env::args().collect();
-        if args.len() != 5 {
-            return Err(BombermanError::InsufficientInput);
+    pub fn new(settings: &Settings, matches: &Matches) -> Result<Config, BombermanError> {
+        let settings = flag_settings(matches)?.over(settings.clone());
+        let order = settings.coordinates.unwrap_or_default();
+        let coordinates = match order {
+            CoordinateOrder::ColumnRow => [COLUMN_FLAG, ROW_FLAG],
+            CoordinateOrder::RowColumn => [ROW_FLAG, COLUMN_FLAG],
+        };
+        let [input, mut output, mut first, mut second] = fill_slots(
+            matches,
+            [INPUT_FLAG, OUTPUT_FLAG, coordinates[0], coordinates[1]],
+        )?;
+        if first.is_some() != second.is_some()
+            && settings.output.is_some()
+            && !matches.is_set(OUTPUT_FLAG)
+        {
+            [_, first, second] = fill_slots(matches, [INPUT_FLAG, coordinates[0], coordinates[1]])?;
+            output = None;
         }
-
-        let x = get_coordinate(&args[X_IDX])?;
-        let y = get_coordinate(&args[Y_IDX])?;
+        let (column, row) = match order {
+            CoordinateOrder::ColumnRow => (first, second),
+            CoordinateOrder::RowColumn => (second, first),
+        };
+        let (x, y) = match (row, column) {
+            (Some(x), Some(y)) => (Some(get_coordinate(x)?), Some(get_coordinate(y)?)),
+            (None, None) => (None, None),
+            _ => return Err(BombermanError::InsufficientInput),
+        };
+        let (input, output) = match (input, output.or(settings.output.as_deref())) {
+            (Some(input), Some(output)) => (input, output),
+            _ => return Err(BombermanError::InsufficientInput),
+        };
         Ok(Config {
-            name_input: args[INPUT_IDX].clone(),
-            path_output: args[OUTPUT_IDX].clone() + &args[INPUT_IDX].clone(),
+            name_input: input.to_string(),
+            path_output: output_path(input, output),
             x,
             y,
+            format: settings.format.unwrap_or_default(),
+            strict: matches.is_set(STRICT_FLAG),
+            show: matches.is_set(SHOW_FLAG),
+            force: matches.is_set(FORCE_FLAG),
+            trace: matches
+                .value(TRACE_FLAG)
+                .map(LineFormat::from_name)
+                .transpose()?,
+            rules: settings.rules.unwrap_or_default(),
         })
     }
+
+    /// Crea una nueva instancia de `Config` a partir de una lista de argumentos, sin
+    /// configuracion del proyecto.
+    ///
+    /// # Argumentos
+    ///
+    /// * `args`: Los argumentos, con el mismo formato que los de línea de comandos (incluyendo el
+    ///   nombre del programa). Ver `new`.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve los mismos errores que `cli::parse` y `new`.
+    ///
+    pub fn from_args(args: &[String]) -> Result<Config, BombermanError> {
+        let matches = cli::parse(FLAGS, args.get(1..).unwrap_or_default())?;
+        Config::new(&Settings::default(), &matches)
+    }
+
+    /// Prepara el archivo de salida antes de la ejecucion: crea las carpetas que le falten y
+    /// verifica que no exista, salvo que se haya pedido sobrescribirlo con `--force`. Si la
+    /// salida es `-` (la salida estándar) no hace nada.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::OutputExists` si el archivo ya existe y no se indico `--force`,
+    /// y `BombermanError::OutputPathError` si no se pueden crear las carpetas.
+    pub fn prepare_output(&self) -> Result<(), BombermanError> {
+        self.prepare_path(&self.path_output)
+    }
+
+    /// Prepara otro archivo que se escribe junto a la salida (por ejemplo el reporte de `diff`)
+    /// con las mismas reglas que `prepare_output`.
+    ///
+    /// # Argumentos
+    ///
+    /// * `path`: Ruta del archivo a escribir.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve los mismos errores que `prepare_output`.
+    pub fn prepare_path(&self, path: &str) -> Result<(), BombermanError> {
+        if path == STANDARD_STREAM {
+            return Ok(());
+        }
+        if Path::new(path).exists() && !self.force {
+            return Err(BombermanError::OutputExists);
+        }
+        file_io::create_parent_dir(path)
+    }
+
+    /// Devuelve la fila y la columna de la bomba a detonar: las de la configuracion si se
+    /// indicaron, o si no las del encabezado `# detonate:` del tablero.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InsufficientInput` si no se indico la coordenada y el tablero no
+    /// tiene `# detonate:` en su encabezado, y `BombermanError::InvalidCoordinate` si no entra en
+    /// un `u32`.
+    pub fn bomb(&self, game: &Game) -> Result<(u32, u32), BombermanError> {
+        match (self.x, self.y, game.header().detonate) {
+            (Some(x), Some(y), _) | (_, _, Some((x, y))) => cell(x, y),
+            _ => Err(BombermanError::InsufficientInput),
+        }
+    }
+}
+
+/// Capa de configuracion con los valores indicados por opciones (`--output` y `--format`).
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidFormat` si el formato pedido no existe.
+fn flag_settings(matches: &Matches) -> Result<Settings, BombermanError> {
+    Ok(Settings {
+        output: matches.value(OUTPUT_FLAG).map(str::to_string),
+        format: matches
+            .value(FORMAT_FLAG)
+            .map(Format::from_name)
+            .transpose()?,
+        ..Settings::default()
+    })
+}
+
+/// Devuelve el valor de cada una de las opciones `slots`: el de la opcion si se indico o, si
+/// no, el siguiente argumento posicional. Queda `None` si no hay mas posicionales.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InsufficientInput` si sobran argumentos posicionales.
+fn fill_slots<'a, const N: usize>(
+    matches: &'a Matches,
+    slots: [&str; N],
+) -> Result<[Option<&'a str>; N], BombermanError> {
+    let mut positional = matches.positional.iter().map(String::as_str);
+    let values = slots.map(|flag| matches.value(flag).or_else(|| positional.next()));
+    match positional.next() {
+        Some(_) => Err(BombermanError::InsufficientInput),
+        None => Ok(values),
+    }
+}
+
+/// Arma la ruta del archivo de salida.
+///
+/// Normalmente es la carpeta de salida unida al nombre del archivo de entrada, sin las carpetas
+/// de su ruta. Si la salida es `-` se usa la salida estándar, y si la entrada es `-` (la entrada
+/// estándar, que no tiene nombre) el argumento de salida se usa como ruta completa del archivo.
+fn output_path(input: &str, output: &str) -> String {
+    if output == STANDARD_STREAM || input == STANDARD_STREAM {
+        return output.to_string();
+    }
+    match Path::new(input).file_name() {
+        Some(name) => Path::new(output).join(name).to_string_lossy().into_owned(),
+        None => output.to_string(),
+    }
 }
 
 /// Convierte una cadena en un número entero no negativo.
@@ -78,13 +344,26 @@ impl Config {
 /// exitosa. Si la conversión falla o el número es negativo, se devuelve un error de tipo
 /// `BombermanError` que indica que la coordenada no es válida.
 ///
-fn get_coordinate(s: &str) -> Result<usize, BombermanError> {
+pub fn get_coordinate(s: &str) -> Result<usize, BombermanError> {
     match s.parse::<usize>() {
         Ok(value) => Ok(value),
         Err(_) => Err(BombermanError::InvalidCoordinate),
     }
 }
 
+/// Convierte la fila `x` y la columna `y` leidas como `usize` a las coordenadas del juego.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidCoordinate` si alguna no entra en un `u32`, en lugar de
+/// truncarla a otra casilla.
+pub fn cell(x: usize, y: usize) -> Result<(u32, u32), BombermanError> {
+    match (u32::try_from(x), u32::try_from(y)) {
+        (Ok(x), Ok(y)) => Ok((x, y)),
+        _ => Err(BombermanError::InvalidCoordinate),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +381,140 @@ mod tests {
         let result = get_coordinate(&input);
         assert_eq!(result, Err(BombermanError::InvalidCoordinate));
     }
+
+    #[test]
+    fn test_from_args() {
+        let args: Vec<String> = ["bomberman", "tablero.txt", "out/", "2", "4"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let conf = Config::from_args(&args).unwrap();
+        assert_eq!(conf.name_input, "tablero.txt");
+        assert_eq!(conf.path_output, "out/tablero.txt");
+        assert_eq!((conf.x, conf.y), (Some(4), Some(2)));
+    }
+
+    #[test]
+    fn test_bomb_from_header() {
+        let args: Vec<String> = ["bomberman", "tablero.txt", "out/"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let conf = Config::from_args(&args).unwrap();
+        let game = Game::from_text("# detonate: 1 0\n_ B1\n_ _\n").unwrap();
+        assert_eq!(conf.bomb(&game), Ok((0, 1)));
+        let game = Game::from_text("_ B1\n_ _\n").unwrap();
+        assert_eq!(conf.bomb(&game), Err(BombermanError::InsufficientInput));
+    }
+
+    #[test]
+    fn test_cell_rejects_truncated_coordinates() {
+        assert_eq!(cell(4, 2), Ok((4, 2)));
+        assert_eq!(
+            cell(4_294_967_296, 0),
+            Err(BombermanError::InvalidCoordinate)
+        );
+    }
+
+    #[test]
+    fn test_from_args_format_flag() {
+        let args: Vec<String> = ["bomberman", "--format", "json", "t.json", "out/", "0", "1"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let conf = Config::from_args(&args).unwrap();
+        assert_eq!(conf.format, Format::Json);
+        assert_eq!(conf.name_input, "t.json");
+    }
+
+    #[test]
+    fn test_from_args_named_flags() {
+        let args: Vec<String> = ["bomberman", "--row", "4", "t.txt", "--column=2", "out/"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let conf = Config::from_args(&args).unwrap();
+        assert_eq!(conf.name_input, "t.txt");
+        assert_eq!(conf.path_output, "out/t.txt");
+        assert_eq!((conf.x, conf.y), (Some(4), Some(2)));
+    }
+
+    #[test]
+    fn test_new_with_settings() {
+        let settings = Settings {
+            output: Some("salida".to_string()),
+            format: Some(Format::RunLength),
+            coordinates: Some(CoordinateOrder::RowColumn),
+            rules: Some(vec![Rule::DestroyRocks]),
+            ..Settings::default()
+        };
+        let parse = |values: &[&str]| {
+            let args: Vec<String> = values.iter().map(|s| s.to_string()).collect();
+            cli::parse(FLAGS, &args).unwrap()
+        };
+
+        let conf = Config::new(&settings, &parse(&["t.txt", "4", "2"])).unwrap();
+        assert_eq!(conf.path_output, "salida/t.txt");
+        assert_eq!((conf.x, conf.y), (Some(4), Some(2)));
+        assert_eq!(conf.format, Format::RunLength);
+        assert_eq!(conf.rules, vec![Rule::DestroyRocks]);
+
+        let conf = Config::new(&settings, &parse(&["t.txt"])).unwrap();
+        assert_eq!((conf.path_output.as_str(), conf.x), ("salida/t.txt", None));
+        let conf =
+            Config::new(&settings, &parse(&["t.txt", "--output", "otra", "4", "2"])).unwrap();
+        assert_eq!(conf.path_output, "otra/t.txt");
+        assert_eq!((conf.x, conf.y), (Some(4), Some(2)));
+
+        let args = [
+            "t.txt", "out", "4", "2", "--format", "plain", "--column", "1",
+        ];
+        assert_eq!(
+            Config::new(&settings, &parse(&args)).unwrap_err(),
+            BombermanError::InsufficientInput
+        );
+        let conf = Config::new(&settings, &parse(&args[..6])).unwrap();
+        assert_eq!(conf.path_output, "out/t.txt");
+        assert_eq!((conf.x, conf.y), (Some(4), Some(2)));
+        assert_eq!(conf.format, Format::Plain);
+    }
+
+    #[test]
+    fn test_output_path_joins_file_name() {
+        assert_eq!(output_path("tests/inputs/x.txt", "out"), "out/x.txt");
+        assert_eq!(output_path("x.txt", "out/"), "out/x.txt");
+    }
+
+    #[test]
+    fn test_prepare_output() {
+        let dir = std::env::temp_dir().join(format!("bomberman_prepare_{}", std::process::id()));
+        let mut conf = Config {
+            path_output: dir.join("nueva/x.txt").to_string_lossy().into_owned(),
+            ..Config::default()
+        };
+        assert_eq!(conf.prepare_output(), Ok(()));
+        assert!(dir.join("nueva").is_dir());
+
+        std::fs::write(&conf.path_output, "").unwrap();
+        assert_eq!(conf.prepare_output(), Err(BombermanError::OutputExists));
+        conf.force = true;
+        assert_eq!(conf.prepare_output(), Ok(()));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_output_path_standard_streams() {
+        assert_eq!(output_path("tablero.txt", "-"), "-");
+        assert_eq!(output_path("-", "out/final.txt"), "out/final.txt");
+        assert_eq!(output_path("-", "-"), "-");
+    }
+
+    #[test]
+    fn test_from_args_insufficient() {
+        let args = vec!["bomberman".to_string()];
+        assert_eq!(
+            Config::from_args(&args).unwrap_err(),
+            BombermanError::InsufficientInput
+        );
+    }
 }
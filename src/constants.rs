@@ -1,7 +1,3 @@
-pub const INPUT_IDX: usize = 1;
-pub const OUTPUT_IDX: usize = 2;
-pub const Y_IDX: usize = 3;
-pub const X_IDX: usize = 4;
 pub const EMPTY_SQUARE: &str = "_";
 pub const ENEMY: char = 'F';
 pub const NORMAL_BOMB: char = 'B';
@@ -14,3 +10,5 @@ pub const RIGHT: char = 'R';
 pub const UP: char = 'U';
 pub const DOWN: char = 'D';
 pub const MAX_LIFE: u32 = 3;
+pub const STANDARD_STREAM: &str = "-";
+pub const COMMENT: char = '#';
@@ -1,10 +1,12 @@
+use crate::constants::{DOWN, LEFT, RIGHT, UP};
+
 /// Representa una coordenada en un sistema bidimensional con valores enteros no negativos.
 ///
 /// `Coordinate` se utiliza para representar posiciones en un tablero o en cualquier espacio
 /// bidimensional donde `x` y `y` son las coordenadas en los ejes horizontal y vertical,
 /// respectivamente.
 ///
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
 pub struct Coordinate {
     pub x: u32,
     pub y: u32,
@@ -69,6 +71,26 @@ impl Coordinate {
         }
         Some(Coordinate::new(self.x, self.y - 1, self.max_value))
     }
+
+    /// Desplaza la coordenada una casilla en la direccion indicada (`U`, `D`, `L` o `R`), con las
+    /// mismas reglas que `up`, `down`, `left` y `right`. Devuelve `None` si la direccion no es valida.
+    pub fn towards(&self, direction: char) -> Option<Coordinate> {
+        match direction {
+            UP => self.up(),
+            DOWN => self.down(),
+            LEFT => self.left(),
+            RIGHT => self.right(),
+            _ => None,
+        }
+    }
+}
+
+/// Muestra la coordenada con el mismo orden que se usa en la linea de comandos y en el enunciado:
+/// primero la columna (`y`) y luego la fila (`x`).
+impl std::fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.y, self.x)
+    }
 }
 
 #[cfg(test)]
@@ -129,4 +151,18 @@ mod tests {
         let result = coord.left();
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_towards() {
+        let coord = Coordinate::new(2, 3, 5);
+        assert_eq!(coord.towards(UP), coord.up());
+        assert_eq!(coord.towards(RIGHT), coord.right());
+        assert_eq!(coord.towards('X'), None);
+    }
+
+    #[test]
+    fn test_display_column_first() {
+        let coord = Coordinate::new(4, 2, 6);
+        assert_eq!(coord.to_string(), "(2, 4)");
+    }
 }
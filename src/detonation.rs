@@ -0,0 +1,367 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::board::Board;
+use crate::constants::{DOWN, LEFT, RIGHT, UP};
+use crate::coordinate::Coordinate;
+use crate::item::Item;
+
+/// Decide, segun el item de la casilla, hacia donde continua la rafaga (o `None` si se bloquea).
+type Expansion = fn(&Board, &Coordinate, char) -> Option<char>;
+
+/// Casilla por la que pasa una rafaga.
+///
+/// # Campos
+///
+/// * `coordinate`: Casilla alcanzada.
+/// * `direction`: Direccion en la que viajaba la rafaga al entrar a la casilla (`U`, `D`, `L` o `R`).
+/// * `remaining`: Alcance que le queda a la rafaga luego de esta casilla.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayStep {
+    pub coordinate: Coordinate,
+    pub direction: char,
+    pub remaining: u32,
+}
+
+/// Recorrido de una de las cuatro rafagas que genera una bomba al explotar.
+///
+/// # Campos
+///
+/// * `origin`: Bomba que genero la rafaga.
+/// * `steps`: Casillas alcanzadas, en orden. La primera es la propia bomba.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ray {
+    pub origin: Coordinate,
+    pub steps: Vec<RayStep>,
+}
+
+/// Evento de la propagacion de una detonacion, en el orden en que ocurre.
+///
+/// # Variantes
+///
+/// - `BombDetonated`: Explota la bomba `item` en `bomb`.
+/// - `RayStarted`: La bomba `origin` inicia una rafaga hacia `direction` con alcance `range`.
+/// - `CellEntered`: Una rafaga entra a una casilla. `remaining` es el alcance que le queda al salir.
+/// - `Deflected`: Un desvio cambia la direccion de la rafaga de `from` a `to`.
+/// - `Blocked`: La rafaga se detiene contra `item`, una roca o una pared.
+/// - `EnemyDamaged`: La rafaga impacta al enemigo `item`; `hits` es la cantidad de rafagas que
+///   lleva recibidas en la detonacion.
+/// - `ChainedBomb`: Una rafaga de la bomba `by` alcanza la bomba `item` en `bomb` y la hace explotar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+    BombDetonated {
+        bomb: Coordinate,
+        item: Item,
+    },
+    RayStarted {
+        origin: Coordinate,
+        direction: char,
+        range: u32,
+    },
+    CellEntered {
+        coordinate: Coordinate,
+        direction: char,
+        remaining: u32,
+    },
+    Deflected {
+        coordinate: Coordinate,
+        from: char,
+        to: char,
+    },
+    Blocked {
+        coordinate: Coordinate,
+        item: Item,
+    },
+    EnemyDamaged {
+        coordinate: Coordinate,
+        item: Item,
+        hits: u32,
+    },
+    ChainedBomb {
+        bomb: Coordinate,
+        item: Item,
+        by: Coordinate,
+    },
+}
+
+/// Resultado de detonar una bomba, sin aplicar el daño al tablero.
+///
+/// # Campos
+///
+/// * `affected`: Cantidad de rafagas que recibe cada casilla alcanzada por la cadena de explosiones.
+/// * `chain`: Bombas detonadas, en el orden en que explotaron. La primera es la bomba inicial.
+/// * `rays`: Recorrido de cada rafaga, en el orden en que se iniciaron.
+/// * `triggers`: Para cada bomba detonada en cadena, el indice en `rays` de la rafaga que la hizo explotar.
+/// * `trace`: Eventos de la propagacion, en el orden en que ocurrieron.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DetonationReport {
+    pub affected: HashMap<Coordinate, u32>,
+    pub chain: Vec<Coordinate>,
+    pub rays: Vec<Ray>,
+    pub triggers: HashMap<Coordinate, usize>,
+    pub trace: Vec<TraceEvent>,
+}
+
+impl DetonationReport {
+    /// Devuelve cada camino por el que la cadena de explosiones llega a `target`, uno por rafaga
+    /// que lo impacta.
+    ///
+    /// Cada camino empieza en la bomba inicial y concatena los tramos de las rafagas que fueron
+    /// detonando las bombas intermedias, hasta llegar a `target`.
+    pub fn paths_to(&self, target: &Coordinate) -> Vec<Vec<RayStep>> {
+        let mut paths = Vec::new();
+        for ray in &self.rays {
+            for (i, step) in ray.steps.iter().enumerate() {
+                if step.coordinate == *target {
+                    let mut path = self.path_to_bomb(&ray.origin);
+                    path.extend_from_slice(&ray.steps[..=i]);
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Camino desde la bomba inicial hasta la casilla anterior a `bomb`, siguiendo las rafagas que
+    /// la detonaron en cadena.
+    fn path_to_bomb(&self, bomb: &Coordinate) -> Vec<RayStep> {
+        let ray = match self.triggers.get(bomb).and_then(|i| self.rays.get(*i)) {
+            Some(ray) => ray,
+            None => return Vec::new(),
+        };
+        let position = ray
+            .steps
+            .iter()
+            .position(|step| step.coordinate == *bomb)
+            .unwrap_or(ray.steps.len());
+        let mut path = self.path_to_bomb(&ray.origin);
+        path.extend_from_slice(&ray.steps[..position]);
+        path
+    }
+}
+
+/// Calcula la cadena de explosiones que se inicia al detonar la bomba en `bomb`, sin modificar el tablero.
+///
+/// Devuelve `None` si en la coordenada no hay una bomba.
+pub fn detonate(board: &Board, bomb: &Coordinate) -> Option<DetonationReport> {
+    let (range, effect) = bomb_effect(board.item_at(bomb))?;
+    let mut explosion = Explosion {
+        board,
+        report: DetonationReport::default(),
+        detonated_bombs: HashSet::new(),
+    };
+    explosion.detonate_explosion(bomb, range, effect);
+    Some(explosion.report)
+}
+
+/// Aplica al tablero el daño de una detonacion: los enemigos pierden una vida por rafaga recibida
+/// (y desaparecen al llegar a cero) y las bombas alcanzadas se consumen.
+pub fn apply_damage(board: &mut Board, affected: &HashMap<Coordinate, u32>) {
+    for (coordinate, damage) in affected {
+        match board.item_at(coordinate) {
+            Item::Enemy(life) => {
+                if life <= *damage {
+                    board.set(*coordinate, Item::Empty);
+                } else {
+                    board.set(*coordinate, Item::Enemy(life - damage));
+                }
+            }
+            Item::NormalBomb(_) | Item::TransferBomb(_) => board.set(*coordinate, Item::Empty),
+            _ => (),
+        }
+    }
+}
+
+/// Elimina del tablero las rocas alcanzadas por alguna rafaga (regla `destroy-rocks`).
+pub fn destroy_rocks(board: &mut Board, affected: &HashMap<Coordinate, u32>) {
+    for coordinate in affected.keys() {
+        if board.item_at(coordinate) == Item::Rock {
+            board.set(*coordinate, Item::Empty);
+        }
+    }
+}
+
+/// Devuelve el alcance y el efecto de la rafaga del item, si es una bomba.
+fn bomb_effect(item: Item) -> Option<(u32, Expansion)> {
+    match item {
+        Item::NormalBomb(range) => Some((range, normal_bomb_effect)),
+        Item::TransferBomb(range) => Some((range, normal_transfer_effect)),
+        _ => None,
+    }
+}
+
+fn normal_bomb_effect(board: &Board, coordinate: &Coordinate, direction: char) -> Option<char> {
+    match board.item_at(coordinate) {
+        Item::Rock | Item::Wall => None,
+        Item::Deflection(d) => Some(d),
+        _ => Some(direction),
+    }
+}
+
+fn normal_transfer_effect(board: &Board, coordinate: &Coordinate, direction: char) -> Option<char> {
+    match board.item_at(coordinate) {
+        Item::Wall => None,
+        Item::Deflection(d) => Some(d),
+        _ => Some(direction),
+    }
+}
+
+/// Estado de una cadena de explosiones en curso.
+struct Explosion<'a> {
+    board: &'a Board,
+    report: DetonationReport,
+    detonated_bombs: HashSet<Coordinate>,
+}
+
+impl Explosion<'_> {
+    fn detonate_explosion(&mut self, bomb: &Coordinate, range: u32, g: Expansion) {
+        if range == 0 {
+            return;
+        }
+
+        self.detonated_bombs.insert(*bomb);
+        self.report.chain.push(*bomb);
+        self.report.trace.push(TraceEvent::BombDetonated {
+            bomb: *bomb,
+            item: self.board.item_at(bomb),
+        });
+
+        for direction in [RIGHT, LEFT, UP, DOWN] {
+            self.report.trace.push(TraceEvent::RayStarted {
+                origin: *bomb,
+                direction,
+                range,
+            });
+            self.report.rays.push(Ray {
+                origin: *bomb,
+                steps: Vec::new(),
+            });
+            let ray = self.report.rays.len() - 1;
+            self.expansive_wave(ray, bomb, range + 1, direction, g);
+        }
+    }
+
+    /// Avanza la rafaga `ray` casilla por casilla, registrando su recorrido y detonando las bombas
+    /// que encuentra, hasta agotar su alcance, salir del tablero o ser bloqueada.
+    fn expansive_wave(
+        &mut self,
+        ray: usize,
+        coordinate: &Coordinate,
+        range: u32,
+        direction: char,
+        g: Expansion,
+    ) {
+        if range == 0 {
+            return;
+        }
+        let hits = self.report.affected.entry(*coordinate).or_insert(0);
+        *hits += 1;
+        let hits = *hits;
+        self.report.rays[ray].steps.push(RayStep {
+            coordinate: *coordinate,
+            direction,
+            remaining: range - 1,
+        });
+        self.report.trace.push(TraceEvent::CellEntered {
+            coordinate: *coordinate,
+            direction,
+            remaining: range - 1,
+        });
+
+        let item = self.board.item_at(coordinate);
+        if let Item::Enemy(_) = item {
+            self.report.trace.push(TraceEvent::EnemyDamaged {
+                coordinate: *coordinate,
+                item,
+                hits,
+            });
+        }
+        if !self.detonated_bombs.contains(coordinate) {
+            if let Some((r, effect)) = bomb_effect(item) {
+                self.report.triggers.insert(*coordinate, ray);
+                self.report.trace.push(TraceEvent::ChainedBomb {
+                    bomb: *coordinate,
+                    item,
+                    by: self.report.rays[ray].origin,
+                });
+                self.detonate_explosion(coordinate, r, effect);
+            }
+        }
+
+        let direction = match (g(self.board, coordinate, direction), item) {
+            (Some(to), Item::Deflection(_)) => {
+                self.report.trace.push(TraceEvent::Deflected {
+                    coordinate: *coordinate,
+                    from: direction,
+                    to,
+                });
+                to
+            }
+            (Some(direction), _) => direction,
+            (None, _) => {
+                self.report.trace.push(TraceEvent::Blocked {
+                    coordinate: *coordinate,
+                    item,
+                });
+                return;
+            }
+        };
+        let prox_coordinate = match coordinate.towards(direction) {
+            Some(coordinate) => coordinate,
+            None => return,
+        };
+        self.expansive_wave(ray, &prox_coordinate, range - 1, direction, g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_detonate_chain_order() {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        let board = game.board();
+        let report = detonate(board, &board.coordinate(0, 0)).unwrap();
+        assert_eq!(
+            report.chain,
+            vec![
+                board.coordinate(0, 0),
+                board.coordinate(2, 0),
+                board.coordinate(2, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detonate_not_a_bomb() {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        let board = game.board();
+        assert_eq!(detonate(board, &board.coordinate(0, 1)), None);
+    }
+
+    #[test]
+    fn test_paths_to_follow_chain_and_deflection() {
+        let game = Game::from_file("./tests/inputs/catedra_3.txt").unwrap();
+        let board = game.board();
+        let report = detonate(board, &board.coordinate(4, 0)).unwrap();
+        let paths = report.paths_to(&board.coordinate(2, 4));
+
+        assert_eq!(paths.len(), 2);
+        let cells: Vec<Coordinate> = paths[0].iter().map(|step| step.coordinate).collect();
+        assert_eq!(
+            cells,
+            vec![
+                board.coordinate(4, 0),
+                board.coordinate(4, 1),
+                board.coordinate(4, 2),
+                board.coordinate(4, 3),
+                board.coordinate(4, 4),
+                board.coordinate(3, 4),
+                board.coordinate(2, 4),
+            ]
+        );
+        assert_eq!(paths[0][4].direction, RIGHT);
+        assert_eq!(paths[0][5].direction, UP);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::coordinate::Coordinate;
+use crate::game::Game;
+use crate::i18n;
+use crate::item::Item;
+
+/// Cambio en una casilla entre dos estados del juego.
+///
+/// # Campos
+///
+/// * `coordinate`: Posicion de la casilla.
+/// * `before`: Item que habia en la casilla en el estado inicial.
+/// * `after`: Item que hay en la casilla en el estado final.
+#[derive(Debug, PartialEq)]
+pub struct CellChange {
+    pub coordinate: Coordinate,
+    pub before: Item,
+    pub after: Item,
+}
+
+impl std::fmt::Display for CellChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.before, self.after) {
+            (Item::Enemy(from), Item::Enemy(to)) => {
+                let args: [&dyn std::fmt::Display; 5] =
+                    [&self.coordinate, &self.before, &self.after, &from, &to];
+                write!(f, "{}", i18n::DIFF_ENEMY_DAMAGED.fill(&args))
+            }
+            (Item::Enemy(_), Item::Empty) => {
+                let args: [&dyn std::fmt::Display; 2] = [&self.coordinate, &self.before];
+                write!(f, "{}", i18n::DIFF_ENEMY_DEFEATED.fill(&args))
+            }
+            (Item::NormalBomb(_) | Item::TransferBomb(_), Item::Empty) => {
+                let args: [&dyn std::fmt::Display; 2] = [&self.coordinate, &self.before];
+                write!(f, "{}", i18n::DIFF_BOMB_USED.fill(&args))
+            }
+            _ => write!(f, "{}: {} -> {}", self.coordinate, self.before, self.after),
+        }
+    }
+}
+
+/// Compara dos estados del juego casilla por casilla y devuelve los cambios, ordenados por fila y columna.
+///
+/// Si los tableros tienen distinta dimension, las casillas que solo existen en uno de ellos se
+/// comparan contra una casilla vacia.
+pub fn diff(before: &Game, after: &Game) -> Vec<CellChange> {
+    let dimension = before.dimension().max(after.dimension());
+    let mut changes = Vec::new();
+    for x in 0..dimension {
+        for y in 0..dimension {
+            let old = item_at(before, x, y);
+            let new = item_at(after, x, y);
+            if old != new {
+                changes.push(CellChange {
+                    coordinate: Coordinate::new(x, y, dimension - 1),
+                    before: old,
+                    after: new,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Arma una vista de texto con el tablero inicial a la izquierda y el final a la derecha,
+/// separados por ` | ` y con las columnas alineadas.
+pub fn side_by_side(before: &Game, after: &Game) -> String {
+    let left = board_rows(before);
+    let right = board_rows(after);
+    let width = left.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    let mut view = String::new();
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).map(String::as_str).unwrap_or("");
+        let r = right.get(i).map(String::as_str).unwrap_or("");
+        view.push_str(format!("{:<width$} | {}", l, r, width = width).trim_end());
+        view.push('\n');
+    }
+    view
+}
+
+fn item_at(game: &Game, x: u32, y: u32) -> Item {
+    if x >= game.dimension() || y >= game.dimension() {
+        return Item::Empty;
+    }
+    game.get(x, y)
+}
+
+/// Devuelve las filas del tablero con cada casilla rellenada al ancho del item mas largo.
+fn board_rows(game: &Game) -> Vec<String> {
+    let dimension = game.dimension();
+    let cell_width = (0..dimension)
+        .flat_map(|x| (0..dimension).map(move |y| (x, y)))
+        .map(|(x, y)| game.get(x, y).to_string().len())
+        .max()
+        .unwrap_or(1);
+    (0..dimension)
+        .map(|x| {
+            let cells: Vec<String> = (0..dimension)
+                .map(|y| format!("{:<width$}", game.get(x, y).to_string(), width = cell_width))
+                .collect();
+            cells.join(" ").trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_catedra_1() {
+        let before = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        let mut after = before.clone();
+        after.denotate_bomb(0, 0).unwrap();
+
+        let changes: Vec<String> = diff(&before, &after)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "(0, 0): bomba B2 consumida",
+                "(4, 0): enemigo F1 eliminado",
+                "(0, 2): bomba B5 consumida",
+                "(4, 2): bomba B2 consumida",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_enemy_damaged() {
+        let before = Game::from_file("./tests/inputs/transfer_bomb.txt").unwrap();
+        let mut after = before.clone();
+        after.denotate_bomb(0, 0).unwrap();
+
+        let changes = diff(&before, &after);
+        assert_eq!(changes[1].to_string(), "(2, 0): enemigo F1 eliminado");
+        assert_eq!(
+            changes[2].to_string(),
+            "(0, 1): enemigo F2 -> F1 (vida 2 -> 1)"
+        );
+    }
+
+    #[test]
+    fn test_diff_same_game_is_empty() {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        assert!(diff(&game, &game).is_empty());
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let before = Game::from_file("./tests/inputs/deviation_down.txt").unwrap();
+        let mut after = before.clone();
+        after.denotate_bomb(0, 1).unwrap();
+        assert_eq!(
+            side_by_side(&before, &after),
+            "R  B2 DD | R  _  DD\n_  W  F1 | _  W  _\nR  _  W  | R  _  W\n"
+        );
+    }
+}
@@ -1,5 +1,10 @@
 use std::{fs::File, io::Write};
 
+use crate::constants::STANDARD_STREAM;
+use crate::format::LineFormat;
+use crate::i18n::{Lang, Message};
+use crate::json::JsonValue;
+
 /// Enumeración que representa los posibles errores que pueden ocurrir durante la ejecución del programa Bomberman.
 ///
 /// Cada variante de este enum representa un tipo específico de error que puede ocurrir, y se utiliza para
@@ -22,6 +27,24 @@ use std::{fs::File, io::Write};
 /// - `NonSquareBoardError`: Indica que el tablero no tiene dimensiones cuadradas.
 /// - `InvalidEnemyLife`: Indica que la vida de un enemigo es inválida.
 /// - `EmptyFileError`: Indica que el archivo de entrada está vacío.
+/// - `InvalidJson`: Indica que el archivo de entrada no es JSON válido.
+/// - `InvalidFormat`: Indica que el formato pedido para la entrada o la salida no existe.
+/// - `InvalidToken`: Envuelve un error de parseo de un item junto con su ubicación en el archivo de entrada.
+/// - `InvalidRowWidth`: Indica que una fila tiene una cantidad de casillas distinta a la esperada.
+/// - `InvalidHeader`: Indica que un valor del encabezado del archivo de entrada no es válido.
+/// - `MissingDetonation`: Indica que un caso de un archivo con varios tableros no indica la bomba a detonar.
+/// - `InvalidRunLength`: Indica que una casilla del formato comprimido no tiene item o repite cero veces.
+/// - `UnknownFlag`: Indica que una opción de la línea de comandos no existe para el comando.
+/// - `MissingFlagValue`: Indica que a una opción de la línea de comandos le falta su valor.
+/// - `InvalidFlagValue`: Indica que el valor de una opción de la línea de comandos no es válido.
+/// - `TooManyItems`: Indica que los items pedidos para generar un tablero no entran en él.
+/// - `BoardTooLarge`: Indica que el tablero es demasiado grande para dibujarlo en PNG.
+/// - `OutputExists`: Indica que el archivo de salida ya existe y no se pidió sobrescribirlo.
+/// - `InvalidLang`: Indica que el idioma pedido no existe.
+/// - `InvalidManifest`: Indica que una línea del manifiesto de un lote de tableros no tiene el formato esperado.
+/// - `InvalidSettings`: Indica que una línea del archivo de configuración (`bomberman.toml`) no es válida.
+/// - `InvalidReplCommand`: Indica que una línea de la consola interactiva no es un comando válido.
+/// - `NothingToUndo`: Indica que en la consola interactiva no hay cambios para deshacer.
 #[derive(Debug, PartialEq)]
 pub enum BombermanError {
     InvalidCoordinate,
@@ -39,6 +62,102 @@ pub enum BombermanError {
     NonSquareBoardError,
     InvalidEnemyLife,
     EmptyFileError,
+    InvalidJson,
+    InvalidFormat,
+    InvalidToken(Box<BombermanError>, TokenLocation),
+    InvalidRowWidth {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidHeader,
+    MissingDetonation,
+    InvalidRunLength,
+    UnknownFlag(String),
+    MissingFlagValue(String),
+    InvalidFlagValue(String),
+    TooManyItems,
+    BoardTooLarge,
+    InvalidManifest {
+        line: usize,
+    },
+    OutputExists,
+    InvalidLang,
+    InvalidSettings {
+        line: usize,
+    },
+    InvalidReplCommand,
+    NothingToUndo,
+}
+
+/// Ubicación de un item inválido: descripción, línea, columna e item.
+const TOKEN_LOCATION: Message = Message {
+    es: "{} (linea {}, columna {}, item '{}')",
+    en: "{} (line {}, column {}, item '{}')",
+};
+/// Sugerencia que se agrega a un item inválido.
+const SUGGESTION: Message = Message {
+    es: ". Quiso decir '{}'?",
+    en: ". Did you mean '{}'?",
+};
+/// Fila de ancho incorrecto: descripción, línea, casillas encontradas y esperadas.
+const ROW_WIDTH_LOCATION: Message = Message {
+    es: "{} (linea {}: {} casillas, se esperaban {})",
+    en: "{} (line {}: {} cells, expected {})",
+};
+/// Error en una línea: descripción y línea.
+const LINE_LOCATION: Message = Message {
+    es: "{} (linea {})",
+    en: "{} (line {})",
+};
+
+/// Categoría de un error, que determina el código de salida del programa.
+///
+/// # Variantes
+///
+/// - `Usage`: Argumentos u opciones de línea de comandos incorrectos (código 2).
+/// - `InputIo`: No se pudo leer un archivo de entrada (código 3).
+/// - `Parse`: El contenido de un archivo de entrada no es válido (código 4).
+/// - `Game`: La detonación pedida no se puede realizar en el tablero (código 5).
+/// - `OutputIo`: No se pudo escribir el archivo de salida (código 6).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorCategory {
+    Usage,
+    InputIo,
+    Parse,
+    Game,
+    OutputIo,
+}
+
+impl ErrorCategory {
+    /// Código de salida del programa para los errores de esta categoría. El código 1 queda para
+    /// los comandos que terminan bien pero con un resultado negativo (por ejemplo, un tablero
+    /// que no se puede ganar).
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCategory::Usage => 2,
+            ErrorCategory::InputIo => 3,
+            ErrorCategory::Parse => 4,
+            ErrorCategory::Game => 5,
+            ErrorCategory::OutputIo => 6,
+        }
+    }
+}
+
+/// Ubicación de un item inválido dentro del archivo de entrada.
+///
+/// # Campos
+///
+/// * `line`: Número de línea, empezando en 1.
+/// * `column`: Columna (en caracteres) donde empieza el item, empezando en 1.
+/// * `token`: El texto del item tal como aparece en el archivo.
+/// * `suggestion`: Un item válido parecido, si se encontró alguno.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenLocation {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub suggestion: Option<String>,
 }
 
 impl BombermanError {
@@ -47,57 +166,294 @@ impl BombermanError {
     /// Esta función devuelve un mensaje de error descriptivo basado en el tipo de error que se ha producido. Los mensajes
     /// de error proporcionados son informativos y ayudan a identificar la causa del error.
     ///
+    /// Si el error corresponde a un item inválido del archivo de entrada, el mensaje incluye la
+    /// línea, la columna y el texto del item, y una sugerencia si se encontró alguna.
+    ///
     /// # Retorno
     ///
-    /// Un valor `&str` que contiene el mensaje descriptivo del error actual.
+    /// Un valor `String` que contiene el mensaje descriptivo del error actual.
     ///
-    pub fn message(&self) -> &str {
+    pub fn message(&self) -> String {
+        self.message_in(Lang::current())
+    }
+
+    /// Igual que `message`, en el idioma `lang`.
+    pub fn message_in(&self, lang: Lang) -> String {
+        let description = self.description().text_in(lang);
         match self {
-            BombermanError::InvalidCoordinate => {
-                "Las coordenadas deben ser enteros de 0 hasta n(#filas de la cantidad de la matriz)"
-            }
-            BombermanError::InsufficientInput => {
-                "Use: cargo new -- tablero.txt path/output.txt x y"
-            }
-            BombermanError::InputPathError => {
-                "El archivo input no exite"
-            }
-            BombermanError::InvalidItem => {
-                "Item no reconocido"
-            }
-            BombermanError::InvalidItemFormat => {
-                "No se reconoce el formato del item"
-            }
-            BombermanError::InvalidEnemyFormat => {
-                "No se cumple el formato del item enemy, use: EXXX con XXX igual a un numero natural"
-            }
-            BombermanError::InvalidNormalBombFormat => {
-                "No se cumple el formato del item Bomba Normal, use: BXXX con XXX igual a un numero natural"
-            }
-            BombermanError::InvalidTransferBombFormat => {
-                "No se cumple el formato del item Bomba de Traspaso, use: SXXX con XXX igual a un numero natural"
-            }
-            BombermanError::InvalidDeflectionFormat => {
-                "No se cumple el formato del item Desvio, use: DX con X una direccion valida (L, R, U, D)"
+            BombermanError::InvalidToken(error, location) => {
+                let mut message = TOKEN_LOCATION.fill_in(
+                    lang,
+                    &[
+                        &error.description().text_in(lang),
+                        &location.line,
+                        &location.column,
+                        &location.token,
+                    ],
+                );
+                if let Some(suggestion) = &location.suggestion {
+                    message.push_str(&SUGGESTION.fill_in(lang, &[suggestion]));
+                }
+                message
             }
-            BombermanError::InvalidBombCoordinate => {
-                "En la coordenada proporcionada no se encontro una bomba"
+            BombermanError::InvalidRowWidth {
+                line,
+                expected,
+                found,
+            } => ROW_WIDTH_LOCATION.fill_in(lang, &[&description, line, found, expected]),
+            BombermanError::InvalidManifest { line } | BombermanError::InvalidSettings { line } => {
+                LINE_LOCATION.fill_in(lang, &[&description, line])
             }
-            BombermanError::OutputPathError => {
-                "El archivo output no se pudo crear"
+            BombermanError::UnknownFlag(name)
+            | BombermanError::MissingFlagValue(name)
+            | BombermanError::InvalidFlagValue(name) => {
+                format!("{} '{}'", description, name)
             }
-            BombermanError::Write => {
-                "No se pudo escribir en el archivo output"
-            }
-            BombermanError::NonSquareBoardError => {
-                "El tablero no es cuadrado"
-            }
-            BombermanError::InvalidEnemyLife => {
-                "La vida de un enemigo esta fuera del rango. Rango: 1 al 3"
-            }
-            BombermanError::EmptyFileError => {
-                "El archivo de entrada esta vacio"
+            _ => description.to_string(),
+        }
+    }
+
+    /// Devuelve el error con la linea de su ubicacion reemplazada por `line`. Los errores sin
+    /// ubicacion se devuelven sin cambios.
+    ///
+    /// Se usa cuando la fila del tablero no coincide con la linea del archivo, por ejemplo si hay
+    /// comentarios antes del tablero.
+    pub fn at_line(self, line: usize) -> BombermanError {
+        match self {
+            BombermanError::InvalidToken(error, location) => {
+                BombermanError::InvalidToken(error, TokenLocation { line, ..location })
             }
+            BombermanError::InvalidRowWidth {
+                expected, found, ..
+            } => BombermanError::InvalidRowWidth {
+                line,
+                expected,
+                found,
+            },
+            error => error,
+        }
+    }
+
+    /// Mensaje fijo asociado a cada tipo de error, sin información de ubicación, en cada idioma.
+    fn description(&self) -> Message {
+        let (es, en) = match self {
+            BombermanError::InvalidToken(error, _) => return error.description(),
+            BombermanError::InvalidCoordinate => (
+                "Las coordenadas deben ser enteros de 0 hasta n(#filas de la cantidad de la matriz)",
+                "Coordinates must be integers from 0 to n - 1 (n = number of rows of the board)",
+            ),
+            BombermanError::InsufficientInput => (
+                "Faltan argumentos, use: cargo run -- run tablero.txt path/output x y (ver cargo run -- --help)",
+                "Missing arguments, use: cargo run -- run board.txt path/output x y (see cargo run -- --help)",
+            ),
+            BombermanError::InputPathError => (
+                "El archivo input no existe",
+                "The input file does not exist",
+            ),
+            BombermanError::InvalidItem => (
+                "Item no reconocido",
+                "Unrecognized item",
+            ),
+            BombermanError::InvalidItemFormat => (
+                "No se reconoce el formato del item",
+                "The item format is not recognized",
+            ),
+            BombermanError::InvalidEnemyFormat => (
+                "No se cumple el formato del item enemy, use: FXXX con XXX igual a un numero natural",
+                "Invalid enemy item, use: FXXX with XXX a natural number",
+            ),
+            BombermanError::InvalidNormalBombFormat => (
+                "No se cumple el formato del item Bomba Normal, use: BXXX con XXX igual a un numero natural",
+                "Invalid normal bomb item, use: BXXX with XXX a natural number",
+            ),
+            BombermanError::InvalidTransferBombFormat => (
+                "No se cumple el formato del item Bomba de Traspaso, use: SXXX con XXX igual a un numero natural",
+                "Invalid transfer bomb item, use: SXXX with XXX a natural number",
+            ),
+            BombermanError::InvalidDeflectionFormat => (
+                "No se cumple el formato del item Desvio, use: DX con X una direccion valida (L, R, U, D)",
+                "Invalid deflection item, use: DX with X a valid direction (L, R, U, D)",
+            ),
+            BombermanError::InvalidBombCoordinate => (
+                "En la coordenada proporcionada no se encontro una bomba",
+                "No bomb was found at the given coordinate",
+            ),
+            BombermanError::OutputPathError => (
+                "El archivo output no se pudo crear",
+                "The output file could not be created",
+            ),
+            BombermanError::Write => (
+                "No se pudo escribir en el archivo output",
+                "Could not write to the output file",
+            ),
+            BombermanError::NonSquareBoardError => (
+                "El tablero no es cuadrado",
+                "The board is not square",
+            ),
+            BombermanError::InvalidEnemyLife => (
+                "La vida de un enemigo esta fuera del rango. Rango: 1 al 3",
+                "An enemy's life is out of range. Range: 1 to 3",
+            ),
+            BombermanError::EmptyFileError => (
+                "El archivo de entrada esta vacio",
+                "The input file is empty",
+            ),
+            BombermanError::InvalidJson => (
+                "El archivo de entrada no es un JSON valido",
+                "The input file is not valid JSON",
+            ),
+            BombermanError::InvalidFormat => (
+                "Formato no reconocido, use: plain, rle o json",
+                "Unrecognized format, use: plain, rle or json",
+            ),
+            BombermanError::InvalidRowWidth { .. } => (
+                "La fila no tiene la misma cantidad de casillas que la primera",
+                "The row does not have the same number of cells as the first one",
+            ),
+            BombermanError::InvalidHeader => (
+                "Encabezado invalido, use: # detonate: x y o # rules: destroy-rocks",
+                "Invalid header, use: # detonate: x y or # rules: destroy-rocks",
+            ),
+            BombermanError::MissingDetonation => (
+                "El caso no indica la bomba a detonar, use: # detonate: x y",
+                "The case does not say which bomb to detonate, use: # detonate: x y",
+            ),
+            BombermanError::InvalidRunLength => (
+                "No se cumple el formato comprimido, use: NX con N entre 1 y el ancho del tablero y X un item",
+                "Invalid compressed format, use: NX with N from 1 to the board width and X an item",
+            ),
+            BombermanError::UnknownFlag(_) => (
+                "Opcion desconocida",
+                "Unknown option",
+            ),
+            BombermanError::MissingFlagValue(_) => (
+                "Falta el valor de la opcion",
+                "Missing value for option",
+            ),
+            BombermanError::InvalidFlagValue(_) => (
+                "Valor invalido para la opcion",
+                "Invalid value for option",
+            ),
+            BombermanError::TooManyItems => (
+                "Los items pedidos no entran en el tablero",
+                "The requested items do not fit on the board",
+            ),
+            BombermanError::BoardTooLarge => (
+                "El tablero es demasiado grande para dibujarlo en PNG (hasta 100 filas), use SVG",
+                "The board is too large to draw as PNG (up to 100 rows), use SVG",
+            ),
+            BombermanError::InvalidManifest { .. } => (
+                "Linea invalida en el manifiesto, use: tablero.txt x y [esperado.txt]",
+                "Invalid manifest line, use: board.txt x y [expected.txt]",
+            ),
+            BombermanError::OutputExists => (
+                "El archivo output ya existe, use --force para sobrescribirlo",
+                "The output file already exists, use --force to overwrite it",
+            ),
+            BombermanError::InvalidSettings { .. } => (
+                "Linea invalida en bomberman.toml, use: clave = \"valor\" con clave output, rules, format, coordinates o lang",
+                "Invalid bomberman.toml line, use: key = \"value\" with key output, rules, format, coordinates or lang",
+            ),
+            BombermanError::InvalidReplCommand => (
+                "Comando no reconocido, use: show, detonate x y, undo, set x y item, rank, save ruta, load ruta, help o quit",
+                "Unrecognized command, use: show, detonate x y, undo, set x y item, rank, save path, load path, help or quit",
+            ),
+            BombermanError::NothingToUndo => (
+                "No hay cambios para deshacer",
+                "There is nothing to undo",
+            ),
+            BombermanError::InvalidLang => (
+                "Idioma no reconocido, use: es o en",
+                "Unrecognized language, use: es or en",
+            ),
+        };
+        Message { es, en }
+    }
+
+    /// Obtiene el codigo del error, un identificador estable pensado para ser procesado por otros
+    /// programas (por ejemplo, en la salida JSON).
+    pub fn code(&self) -> &str {
+        match self {
+            BombermanError::InvalidCoordinate => "invalid_coordinate",
+            BombermanError::InsufficientInput => "insufficient_input",
+            BombermanError::InputPathError => "input_path_error",
+            BombermanError::InvalidItem => "invalid_item",
+            BombermanError::InvalidItemFormat => "invalid_item_format",
+            BombermanError::InvalidEnemyFormat => "invalid_enemy_format",
+            BombermanError::InvalidNormalBombFormat => "invalid_normal_bomb_format",
+            BombermanError::InvalidTransferBombFormat => "invalid_transfer_bomb_format",
+            BombermanError::InvalidDeflectionFormat => "invalid_deflection_format",
+            BombermanError::InvalidBombCoordinate => "invalid_bomb_coordinate",
+            BombermanError::OutputPathError => "output_path_error",
+            BombermanError::Write => "write_error",
+            BombermanError::NonSquareBoardError => "non_square_board",
+            BombermanError::InvalidEnemyLife => "invalid_enemy_life",
+            BombermanError::EmptyFileError => "empty_file",
+            BombermanError::InvalidJson => "invalid_json",
+            BombermanError::InvalidFormat => "invalid_format",
+            BombermanError::InvalidToken(error, _) => error.code(),
+            BombermanError::InvalidRowWidth { .. } => "invalid_row_width",
+            BombermanError::InvalidHeader => "invalid_header",
+            BombermanError::MissingDetonation => "missing_detonation",
+            BombermanError::InvalidRunLength => "invalid_run_length",
+            BombermanError::UnknownFlag(_) => "unknown_flag",
+            BombermanError::MissingFlagValue(_) => "missing_flag_value",
+            BombermanError::InvalidFlagValue(_) => "invalid_flag_value",
+            BombermanError::TooManyItems => "too_many_items",
+            BombermanError::BoardTooLarge => "board_too_large",
+            BombermanError::InvalidManifest { .. } => "invalid_manifest",
+            BombermanError::InvalidSettings { .. } => "invalid_settings",
+            BombermanError::InvalidReplCommand => "invalid_repl_command",
+            BombermanError::NothingToUndo => "nothing_to_undo",
+            BombermanError::OutputExists => "output_exists",
+            BombermanError::InvalidLang => "invalid_lang",
+        }
+    }
+
+    /// Obtiene la categoría del error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            BombermanError::InsufficientInput
+            | BombermanError::InvalidCoordinate
+            | BombermanError::InvalidFormat
+            | BombermanError::UnknownFlag(_)
+            | BombermanError::MissingFlagValue(_)
+            | BombermanError::InvalidFlagValue(_)
+            | BombermanError::TooManyItems
+            | BombermanError::BoardTooLarge
+            | BombermanError::InvalidLang
+            | BombermanError::InvalidReplCommand => ErrorCategory::Usage,
+            BombermanError::InputPathError => ErrorCategory::InputIo,
+            BombermanError::InvalidItem
+            | BombermanError::InvalidItemFormat
+            | BombermanError::InvalidEnemyFormat
+            | BombermanError::InvalidNormalBombFormat
+            | BombermanError::InvalidTransferBombFormat
+            | BombermanError::InvalidDeflectionFormat
+            | BombermanError::NonSquareBoardError
+            | BombermanError::InvalidEnemyLife
+            | BombermanError::EmptyFileError
+            | BombermanError::InvalidJson
+            | BombermanError::InvalidToken(..)
+            | BombermanError::InvalidRowWidth { .. }
+            | BombermanError::InvalidHeader
+            | BombermanError::InvalidRunLength
+            | BombermanError::InvalidManifest { .. }
+            | BombermanError::InvalidSettings { .. } => ErrorCategory::Parse,
+            BombermanError::InvalidBombCoordinate
+            | BombermanError::MissingDetonation
+            | BombermanError::NothingToUndo => ErrorCategory::Game,
+            BombermanError::OutputPathError
+            | BombermanError::Write
+            | BombermanError::OutputExists => ErrorCategory::OutputIo,
+        }
+    }
+
+    /// Arma el mensaje de diagnóstico del error en el formato pedido, sin salto de línea final.
+    pub fn diagnostic(&self, format: LineFormat) -> String {
+        match format {
+            LineFormat::Text => format!("Error: {}", self.message()),
+            LineFormat::Json => self.to_json().to_string(),
         }
     }
 
@@ -115,19 +471,133 @@ impl BombermanError {
     /// Esta función se utiliza para escribir el mensaje de error actual en un archivo. Si la escritura es exitosa,
     /// no se mostrará ningún mensaje adicional en la consola. En caso de error, se mostrará el mensaje de error
     /// en la consola.
+    ///
+    /// Si `path_output` es `-` (la salida estándar), el mensaje se escribe en la salida de errores
+    /// para no mezclarlo con el resultado.
     pub fn send(&self, path_output: String) {
-        let error = format!("Error: {}", self.message());
+        write_error(path_output, format!("Error: {}", self.message()));
+    }
+
+    /// Escribe el error en formato JSON (ver `to_json`) en el archivo `path_output`, con el mismo
+    /// comportamiento que `send` si no se puede escribir el archivo.
+    pub fn send_json(&self, path_output: String) {
+        write_error(path_output, format!("{}\n", self.to_json()));
+    }
 
-        let mut file = match File::create(path_output) {
-            Ok(file) => file,
-            Err(_) => {
-                println!("{}", error);
-                return;
+    /// Representa el error como un objeto JSON con su codigo y su mensaje:
+    /// `{"error":{"code":"...","message":"..."}}`. Si el error tiene ubicacion, el objeto tambien
+    /// incluye `line` y, para los items invalidos, `column`, `token` y, si la hay, `suggestion`.
+    pub fn to_json(&self) -> JsonValue {
+        let mut fields = vec![
+            ("code", JsonValue::String(self.code().to_string())),
+            ("message", JsonValue::String(self.message())),
+        ];
+        match self {
+            BombermanError::InvalidToken(_, location) => {
+                fields.push(("line", JsonValue::Number(location.line as f64)));
+                fields.push(("column", JsonValue::Number(location.column as f64)));
+                fields.push(("token", JsonValue::String(location.token.clone())));
+                if let Some(suggestion) = &location.suggestion {
+                    fields.push(("suggestion", JsonValue::String(suggestion.clone())));
+                }
             }
-        };
-        match file.write_all(error.as_bytes()) {
-            Ok(_) => (),
-            Err(_) => println!("{}", error),
+            BombermanError::InvalidRowWidth { line, .. }
+            | BombermanError::InvalidManifest { line }
+            | BombermanError::InvalidSettings { line } => {
+                fields.push(("line", JsonValue::Number(*line as f64)));
+            }
+            _ => (),
+        }
+        JsonValue::object(vec![("error", JsonValue::object(fields))])
+    }
+}
+
+fn write_error(path_output: String, error: String) {
+    if path_output == STANDARD_STREAM {
+        eprintln!("{}", error.trim_end());
+        return;
+    }
+    let mut file = match File::create(path_output) {
+        Ok(file) => file,
+        Err(_) => {
+            eprintln!("{}", error.trim_end());
+            return;
         }
+    };
+    match file.write_all(error.as_bytes()) {
+        Ok(_) => (),
+        Err(_) => eprintln!("{}", error.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_exit_codes() {
+        assert_eq!(
+            BombermanError::UnknownFlag("--x".into())
+                .category()
+                .exit_code(),
+            2
+        );
+        assert_eq!(BombermanError::InputPathError.category().exit_code(), 3);
+        let token = BombermanError::InvalidToken(
+            Box::new(BombermanError::InvalidItem),
+            TokenLocation {
+                line: 1,
+                column: 1,
+                token: "Z".to_string(),
+                suggestion: None,
+            },
+        );
+        assert_eq!(token.category(), ErrorCategory::Parse);
+        assert_eq!(
+            BombermanError::InvalidBombCoordinate.category().exit_code(),
+            5
+        );
+        assert_eq!(BombermanError::Write.category().exit_code(), 6);
+    }
+
+    #[test]
+    fn test_diagnostic_formats() {
+        let error = BombermanError::EmptyFileError;
+        assert_eq!(
+            error.diagnostic(LineFormat::Text),
+            "Error: El archivo de entrada esta vacio"
+        );
+        assert_eq!(
+            error.diagnostic(LineFormat::Json),
+            r#"{"error":{"code":"empty_file","message":"El archivo de entrada esta vacio"}}"#
+        );
+    }
+
+    #[test]
+    fn test_message_in_each_lang() {
+        let error = BombermanError::InvalidToken(
+            Box::new(BombermanError::InvalidEnemyFormat),
+            TokenLocation {
+                line: 2,
+                column: 3,
+                token: "E1".to_string(),
+                suggestion: Some("F1".to_string()),
+            },
+        );
+        assert_eq!(
+            error.message_in(Lang::Es),
+            "No se cumple el formato del item enemy, use: FXXX con XXX igual a un numero natural \
+             (linea 2, columna 3, item 'E1'). Quiso decir 'F1'?"
+        );
+        assert_eq!(
+            error.message_in(Lang::En),
+            "Invalid enemy item, use: FXXX with XXX a natural number \
+             (line 2, column 3, item 'E1'). Did you mean 'F1'?"
+        );
+        assert_eq!(error.code(), "invalid_enemy_format");
+        assert_eq!(
+            BombermanError::InputPathError.message_in(Lang::Es),
+            "El archivo input no existe"
+        );
     }
 }
@@ -1,18 +1,24 @@
 use std::{
     any::Any,
-    fs::File,
-    io::{BufRead, BufReader},
+    borrow::Cow,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    path::Path,
 };
 
-use crate::error::BombermanError;
+use crate::constants::{COMMENT, STANDARD_STREAM};
+use crate::error::{BombermanError, TokenLocation};
+use crate::i18n;
+use crate::item;
 type Operacion = fn(&str, usize, u32, &mut dyn Any) -> Result<(), BombermanError>;
 
 /// Lee un archivo de entrada en la ruta especificada y aplica una operación personalizada
 /// a cada línea del archivo.
 ///
 /// Esta función toma tres argumentos: una referencia a una cadena de texto `path` que
-/// representa la ruta del archivo de entrada, una función de operación personalizada `process`
-/// que se aplicará a cada línea del archivo, y un puntero mutable a un objeto dinámico `ptr`.
+/// representa la ruta del archivo de entrada (o `-` para leer de la entrada estándar), una
+/// función de operación personalizada `process` que se aplicará a cada línea del archivo, y un
+/// puntero mutable a un objeto dinámico `ptr`.
 ///
 /// Donde `line` es la línea actual del archivo, `row` es el número de fila actual y `ptr`
 /// es un puntero mutable al objeto dinámico que se puede utilizar para almacenar resultados
@@ -30,33 +36,76 @@ pub fn read_input(
     process: Operacion,
     ptr: &mut dyn Any,
 ) -> Result<(), BombermanError> {
-    let file = open_file_for_reading(path)?;
-    let mut reader: BufReader<File> = BufReader::new(file);
-    let mut line = String::new();
-    let mut row: usize = 0;
+    let content = read_to_string(path)?;
+    process_input(&content, max_value, process, ptr)
+}
 
-    while let Ok(bytes_read) = reader.read_line(&mut line) {
-        if bytes_read == 0 {
-            break; // Fin del archivo
-        }
-        process(&line, row, max_value, ptr)?;
-        row += 1;
-        line.clear();
+/// Aplica la operación `process` a cada línea de `content`, igual que `read_input` pero sobre un
+/// texto ya leído.
+///
+/// Las lineas de comentario se saltean, por lo que `row` es la fila del tablero y no
+/// necesariamente la linea del archivo.
+///
+/// # Errores
+///
+/// Devuelve el primer error que devuelva `process`, con su ubicacion corregida a la linea del archivo.
+pub fn process_input(
+    content: &str,
+    max_value: u32,
+    process: Operacion,
+    ptr: &mut dyn Any,
+) -> Result<(), BombermanError> {
+    for (row, (line_number, line)) in board_lines(content).enumerate() {
+        process(line, row, max_value, ptr).map_err(|e| e.at_line(line_number))?;
     }
     Ok(())
 }
+
+/// Indica si la linea es un comentario, es decir si su primer caracter visible es `#`.
+pub fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with(COMMENT)
+}
+
+/// Devuelve las filas del tablero junto con su numero de linea en el archivo (empezando en 1),
+/// salteando las lineas de comentario. Cada fila conserva su salto de linea.
+pub fn board_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(_, line)| !is_comment(line))
+        .map(|(i, line)| (i + 1, line))
+}
+
+/// Obtiene la dimensión del tablero guardado en la ruta especificada (o `-` para la entrada estándar).
+///
+/// Ver `matrix_dimensions`.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InputPathError` si no se puede leer el archivo y los mismos errores
+/// que `matrix_dimensions`.
 pub fn get_matrix_dimensions(path: &str) -> Result<Option<usize>, BombermanError> {
-    let file = open_file_for_reading(path)?;
-    let mut reader: BufReader<File> = BufReader::new(file);
-    let mut line = String::new();
+    let content = read_to_string(path)?;
+    matrix_dimensions(&content)
+}
+
+/// Obtiene la dimensión de un tablero a partir de su texto.
+///
+/// Cuenta las columnas de la primera fila (salteando los comentarios) y verifica que todas las filas tengan la misma
+/// cantidad de columnas y que la cantidad de filas coincida con la de columnas.
+///
+/// Devuelve `Some(n)` con la cantidad de columnas si el tablero es de `n` x `n`, o `None` si
+/// alguna fila tiene una cantidad de columnas distinta a la primera.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::NonSquareBoardError` si todas las filas tienen la misma cantidad de
+/// columnas pero esta no coincide con la cantidad de filas.
+pub fn matrix_dimensions(content: &str) -> Result<Option<usize>, BombermanError> {
     let mut expected_columns: Option<usize> = None;
     let mut row: usize = 0;
 
-    while let Ok(bytes_read) = reader.read_line(&mut line) {
-        if bytes_read == 0 {
-            break; // Fin del archivo
-        }
-
+    for (_, line) in board_lines(content) {
         let columns: Vec<&str> = line.split_whitespace().collect();
 
         // Verificar si este es el primer renglón para establecer el número esperado de columnas
@@ -68,7 +117,6 @@ pub fn get_matrix_dimensions(path: &str) -> Result<Option<usize>, BombermanError
             expected_columns = Some(columns.len());
         }
         row += 1;
-        line.clear();
     }
     if row != expected_columns.unwrap_or(0) {
         return Err(BombermanError::NonSquareBoardError);
@@ -76,6 +124,257 @@ pub fn get_matrix_dimensions(path: &str) -> Result<Option<usize>, BombermanError
     Ok(expected_columns)
 }
 
+/// Separa la linea en items, igual que `split_whitespace`, junto con la columna (en caracteres,
+/// empezando en 1) donde empieza cada uno.
+pub fn tokens_with_columns(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    for (column, (byte, c)) in line.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, byte)),
+            (true, Some((token_column, token_byte))) => {
+                tokens.push((token_column, &line[token_byte..byte]));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((token_column, token_byte)) = start {
+        tokens.push((token_column, &line[token_byte..]));
+    }
+    tokens
+}
+
+/// Indica si el tablero esta en formato comprimido: alguna casilla empieza con la cantidad de
+/// veces que se repite, por ejemplo `12_ W 3_ F2`.
+pub fn is_run_length(content: &str) -> bool {
+    board_lines(content).any(|(_, line)| {
+        line.split_whitespace()
+            .any(|token| token.starts_with(|c: char| c.is_ascii_digit()))
+    })
+}
+
+/// Devuelve el texto del tablero en el formato de texto original, con una casilla por item.
+///
+/// Si el tablero esta en formato comprimido (ver `is_run_length`) expande cada casilla `nX` en
+/// `n` casillas `X`; si no, devuelve el texto sin cambios. Los comentarios se conservan.
+///
+/// Los items se revisan antes de expandirlos, para que los errores indiquen la columna del item
+/// en el archivo y no la del texto expandido.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidToken` con `BombermanError::InvalidRunLength` si alguna
+/// casilla tiene una cantidad sin item, una cantidad igual a cero o mas casillas de las que
+/// entran en una fila del tablero (tantas como filas tiene), y con el error del item si alguno
+/// no es valido.
+pub fn board_text(content: &str) -> Result<Cow<'_, str>, BombermanError> {
+    if !is_run_length(content) {
+        return Ok(Cow::Borrowed(content));
+    }
+    let width = board_lines(content).count();
+    let mut text = String::with_capacity(content.len() * 2);
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        if is_comment(line) {
+            text.push_str(line);
+            continue;
+        }
+        let mut cells: Vec<&str> = Vec::new();
+        for (column, token) in tokens_with_columns(line) {
+            let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            let (count, item) = token.split_at(digits);
+            let count = match count {
+                "" => 1,
+                count => count.parse::<usize>().unwrap_or(0),
+            };
+            if !item.is_empty() {
+                item::parse_token(item, i + 1, column + digits)?;
+            }
+            if count == 0 || item.is_empty() || count > width - cells.len() {
+                let location = TokenLocation {
+                    line: i + 1,
+                    column,
+                    token: token.to_string(),
+                    suggestion: None,
+                };
+                return Err(BombermanError::InvalidToken(
+                    Box::new(BombermanError::InvalidRunLength),
+                    location,
+                ));
+            }
+            cells.extend(std::iter::repeat_n(item, count));
+        }
+        text.push_str(&cells.join(" "));
+        if line.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    Ok(Cow::Owned(text))
+}
+
+/// Diferencia del archivo de entrada con el formato esperado que se corrigio al normalizarlo.
+///
+/// # Variantes
+///
+/// - `ByteOrderMark`: El archivo empezaba con una marca de orden de bytes (BOM), que se quito.
+/// - `CarriageReturns(usize)`: Cantidad de fines de linea de Windows (`\r\n`) convertidos a `\n`.
+/// - `Tabs(Vec<usize>)`: Lineas (empezando en 1) cuyas tabulaciones se reemplazaron por espacios.
+/// - `TrailingBlankLines(usize)`: Cantidad de lineas vacias que se ignoraron al final del archivo.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputWarning {
+    ByteOrderMark,
+    CarriageReturns(usize),
+    Tabs(Vec<usize>),
+    TrailingBlankLines(usize),
+}
+
+impl std::fmt::Display for InputWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputWarning::ByteOrderMark => {
+                write!(f, "{}", i18n::INPUT_BOM.text())
+            }
+            InputWarning::CarriageReturns(count) => {
+                write!(f, "{}", i18n::INPUT_CRLF.fill(&[count]))
+            }
+            InputWarning::Tabs(lines) => {
+                let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+                write!(f, "{}", i18n::INPUT_TABS.fill(&[&lines.join(", ")]))
+            }
+            InputWarning::TrailingBlankLines(count) => {
+                write!(f, "{}", i18n::INPUT_TRAILING_LINES.fill(&[count]))
+            }
+        }
+    }
+}
+
+/// Texto de un tablero normalizado, junto con las advertencias de lo que se corrigio.
+///
+/// # Campos
+///
+/// * `text`: El texto normalizado.
+/// * `warnings`: Las correcciones aplicadas, vacia si el texto ya tenia el formato esperado.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalized {
+    pub text: String,
+    pub warnings: Vec<InputWarning>,
+}
+
+/// Normaliza el texto de un tablero para tolerar archivos editados en otros sistemas: quita la
+/// marca de orden de bytes inicial, convierte los fines de linea `\r\n` en `\n`, reemplaza cada
+/// tabulacion por un espacio (por lo que las columnas de los items no cambian) y quita las lineas
+/// vacias del final, que de otro modo se contarian como filas del tablero.
+pub fn normalize(content: &str) -> Normalized {
+    let mut warnings = Vec::new();
+    let content = match content.strip_prefix('\u{feff}') {
+        Some(content) => {
+            warnings.push(InputWarning::ByteOrderMark);
+            content
+        }
+        None => content,
+    };
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut carriage_returns = 0;
+    let mut tabs = Vec::new();
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let mut line = line.to_string();
+        if line.ends_with("\r\n") {
+            line.truncate(line.len() - 2);
+            line.push('\n');
+            carriage_returns += 1;
+        }
+        if line.contains('\t') {
+            line = line.replace('\t', " ");
+            tabs.push(i + 1);
+        }
+        lines.push(line);
+    }
+
+    let mut blank_lines = 0;
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+        blank_lines += 1;
+    }
+
+    if carriage_returns > 0 {
+        warnings.push(InputWarning::CarriageReturns(carriage_returns));
+    }
+    if !tabs.is_empty() {
+        warnings.push(InputWarning::Tabs(tabs));
+    }
+    if blank_lines > 0 {
+        warnings.push(InputWarning::TrailingBlankLines(blank_lines));
+    }
+    Normalized {
+        text: lines.concat(),
+        warnings,
+    }
+}
+
+/// Lee el contenido completo del archivo en la ruta especificada. Si la ruta es `-`, lee la
+/// entrada estándar.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InputPathError` si el archivo no existe o no se puede leer.
+pub fn read_to_string(path: &str) -> Result<String, BombermanError> {
+    let mut content = String::new();
+    let result = if path == STANDARD_STREAM {
+        io::stdin().read_to_string(&mut content)
+    } else {
+        open_file_for_reading(path)?.read_to_string(&mut content)
+    };
+    match result {
+        Ok(_) => Ok(content),
+        Err(_) => Err(BombermanError::InputPathError),
+    }
+}
+
+/// Escribe `content` en el archivo de la ruta especificada, creándolo o reemplazándolo. Si la ruta
+/// es `-`, escribe en la salida estándar.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::OutputPathError` si no se puede crear el archivo y
+/// `BombermanError::Write` si falla la escritura.
+pub fn write_output(path: &str, content: &str) -> Result<(), BombermanError> {
+    write_bytes(path, content.as_bytes())
+}
+
+/// Igual que `write_output`, para contenido binario (por ejemplo, una imagen).
+///
+/// # Errores
+///
+/// Los mismos que `write_output`.
+pub fn write_bytes(path: &str, content: &[u8]) -> Result<(), BombermanError> {
+    let result = if path == STANDARD_STREAM {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(content).and_then(|_| stdout.flush())
+    } else {
+        let mut writer = BufWriter::new(open_file_for_writing(path)?);
+        writer.write_all(content).and_then(|_| writer.flush())
+    };
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) => Err(BombermanError::Write),
+    }
+}
+
+/// Crea las carpetas de la ruta `path` que todavia no existen, sin crear el archivo.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::OutputPathError` si no se puede crear alguna carpeta.
+pub fn create_parent_dir(path: &str) -> Result<(), BombermanError> {
+    match Path::new(path).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => {
+            fs::create_dir_all(dir).map_err(|_| BombermanError::OutputPathError)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Abre un archivo en modo de lectura en la ruta especificada.
 ///
 /// Esta función toma una referencia a una cadena de texto `path` que representa la
@@ -196,4 +495,64 @@ mod tests {
         let result = get_matrix_dimensions(file_path);
         assert_eq!(result.unwrap_err(), BombermanError::NonSquareBoardError);
     }
+
+    #[test]
+    fn test_board_text_expands_run_length() {
+        let text = board_text("# detonate: 0 0\n2_ B1\nW 2_\n3F1\n").unwrap();
+        assert_eq!(text, "# detonate: 0 0\n_ _ B1\nW _ _\nF1 F1 F1\n");
+        assert!(matches!(
+            board_text("B1 _\n_ _\n").unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_board_text_invalid_run_length() {
+        let error = board_text("2_ B1\n_ 0W _\n3_\n").unwrap_err();
+        assert_eq!(error.code(), "invalid_run_length");
+        assert_eq!(
+            error.message(),
+            "No se cumple el formato comprimido, use: NX con N entre 1 y el ancho del tablero y X un item (linea 2, columna 3, item '0W')"
+        );
+    }
+
+    #[test]
+    fn test_board_text_run_length_limits() {
+        let error = board_text("99999999999_ B1\n").unwrap_err();
+        assert_eq!(error.code(), "invalid_run_length");
+        assert!(board_text("3_ B1\n_ 2_\n3_\n").is_err());
+        assert!(board_text("2_ B1\n_ 2_\n3_\n").is_ok());
+        let error = board_text("_ 18446744073709551615_\n").unwrap_err();
+        assert_eq!(error.code(), "invalid_run_length");
+
+        let error = board_text("3_ ZZ\n3_\n3_\n").unwrap_err();
+        assert_eq!(error.code(), "invalid_item");
+        assert!(error.message().ends_with("(linea 1, columna 4, item 'ZZ')"));
+        let error = board_text("_ 2f1\n3_\n3_\n").unwrap_err();
+        assert!(error
+            .message()
+            .ends_with("(linea 1, columna 4, item 'f1'). Quiso decir 'F1'?"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let normalized = normalize("\u{feff}B1\t_\r\n_ W\r\n\n  \n");
+        assert_eq!(normalized.text, "B1 _\n_ W\n");
+        assert_eq!(
+            normalized.warnings,
+            vec![
+                InputWarning::ByteOrderMark,
+                InputWarning::CarriageReturns(2),
+                InputWarning::Tabs(vec![1]),
+                InputWarning::TrailingBlankLines(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_clean_input_has_no_warnings() {
+        let normalized = normalize("B1 _\n_ W\n");
+        assert_eq!(normalized.text, "B1 _\n_ W\n");
+        assert!(normalized.warnings.is_empty());
+    }
 }
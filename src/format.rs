@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use crate::board::Board;
+use crate::coordinate::Coordinate;
+use crate::detonation::DetonationReport;
+use crate::error::BombermanError;
+use crate::item::Item;
+use crate::json::{self, JsonValue};
+
+/// Formato de los archivos de entrada y salida.
+///
+/// # Variantes
+///
+/// - `Plain`: El formato de texto del enunciado, una fila por linea con las casillas separadas por espacios.
+/// - `RunLength`: El formato de texto comprimido, donde las casillas iguales consecutivas de una
+///   fila se escriben una sola vez precedidas de la cantidad, por ejemplo `12_ W 3_ F2`.
+/// - `Json`: El tablero como una matriz de objetos JSON, por ejemplo `{"type":"enemy","life":2}`.
+///
+/// Los tableros de texto se leen en cualquiera de los dos formatos de texto (se detecta
+/// automaticamente), por lo que `Plain` y `RunLength` solo deciden el formato de la salida.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Plain,
+    RunLength,
+    Json,
+}
+
+impl Format {
+    /// Obtiene el formato a partir de su nombre (`plain`, `rle` o `json`).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidFormat` si el nombre no corresponde a ningun formato.
+    pub fn from_name(name: &str) -> Result<Format, BombermanError> {
+        match name {
+            "plain" => Ok(Format::Plain),
+            "rle" => Ok(Format::RunLength),
+            "json" => Ok(Format::Json),
+            _ => Err(BombermanError::InvalidFormat),
+        }
+    }
+}
+
+/// Formato de los mensajes que se escriben de a una linea: los errores de la salida de errores
+/// (`--error-format text|json`) y el registro de la propagacion (`--trace text|json`).
+///
+/// # Variantes
+///
+/// - `Text`: Una linea de texto, por ejemplo `Error: <mensaje>`.
+/// - `Json`: Un objeto JSON en una sola linea.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl LineFormat {
+    /// Obtiene el formato a partir de su nombre (`text` o `json`).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidFormat` si el nombre no corresponde a ningun formato.
+    pub fn from_name(name: &str) -> Result<LineFormat, BombermanError> {
+        match name {
+            "text" => Ok(LineFormat::Text),
+            "json" => Ok(LineFormat::Json),
+            _ => Err(BombermanError::InvalidFormat),
+        }
+    }
+}
+
+/// Representa un item como objeto JSON, con su tipo y, si corresponde, su vida, alcance o direccion.
+pub fn item_to_json(item: &Item) -> JsonValue {
+    let kind = |name: &str| ("type", JsonValue::String(name.to_string()));
+    let number = |n: u32| JsonValue::Number(n as f64);
+    match item {
+        Item::Enemy(life) => JsonValue::object(vec![kind("enemy"), ("life", number(*life))]),
+        Item::NormalBomb(range) => {
+            JsonValue::object(vec![kind("normal_bomb"), ("range", number(*range))])
+        }
+        Item::TransferBomb(range) => {
+            JsonValue::object(vec![kind("transfer_bomb"), ("range", number(*range))])
+        }
+        Item::Rock => JsonValue::object(vec![kind("rock")]),
+        Item::Wall => JsonValue::object(vec![kind("wall")]),
+        Item::Deflection(direction) => JsonValue::object(vec![
+            kind("deflection"),
+            ("direction", JsonValue::String(direction.to_string())),
+        ]),
+        Item::Empty => JsonValue::object(vec![kind("empty")]),
+    }
+}
+
+/// Lee un item a partir de su objeto JSON.
+///
+/// El objeto se traduce a la notacion del formato de texto (por ejemplo `F2`) y se valida con
+/// `Item::new`, por lo que se aplican las mismas reglas y errores.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidItem` si el tipo no existe, y el error de formato del item
+/// correspondiente si falta su vida, alcance o direccion.
+pub fn item_from_json(value: &JsonValue) -> Result<Item, BombermanError> {
+    let kind = value
+        .get("type")
+        .and_then(JsonValue::as_str)
+        .ok_or(BombermanError::InvalidItem)?;
+    let number = |key: &str, e: BombermanError| match value.get(key).and_then(JsonValue::as_u32) {
+        Some(n) => Ok(n),
+        None => Err(e),
+    };
+    let token = match kind {
+        "empty" => return Ok(Item::Empty),
+        "rock" => "R".to_string(),
+        "wall" => "W".to_string(),
+        "enemy" => format!("F{}", number("life", BombermanError::InvalidEnemyFormat)?),
+        "normal_bomb" => format!(
+            "B{}",
+            number("range", BombermanError::InvalidNormalBombFormat)?
+        ),
+        "transfer_bomb" => format!(
+            "S{}",
+            number("range", BombermanError::InvalidTransferBombFormat)?
+        ),
+        "deflection" => format!(
+            "D{}",
+            value
+                .get("direction")
+                .and_then(JsonValue::as_str)
+                .ok_or(BombermanError::InvalidDeflectionFormat)?
+        ),
+        _ => return Err(BombermanError::InvalidItem),
+    };
+    Item::new(&token)
+}
+
+/// Representa el tablero como una lista de filas, cada una con los objetos JSON de sus casillas.
+pub fn board_to_json(board: &Board) -> JsonValue {
+    JsonValue::Array(
+        (0..board.dimension())
+            .map(|x| {
+                JsonValue::Array(
+                    (0..board.dimension())
+                        .map(|y| item_to_json(&board.get(x, y)))
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Lee un tablero en formato JSON. Se acepta la matriz de items directamente o un objeto que la
+/// contenga en la clave `board`.
+///
+/// # Errores
+///
+/// * `BombermanError::InvalidJson` - Si el texto no es JSON valido o no contiene una matriz.
+/// * `BombermanError::EmptyFileError` - Si la matriz no tiene filas.
+/// * `BombermanError::NonSquareBoardError` - Si alguna fila no tiene tantas casillas como filas tiene la matriz.
+/// * Los errores de `item_from_json` para cada casilla.
+pub fn board_from_json(text: &str) -> Result<Board, BombermanError> {
+    let value = json::parse(text)?;
+    let rows = value
+        .get("board")
+        .unwrap_or(&value)
+        .as_array()
+        .ok_or(BombermanError::InvalidJson)?;
+    if rows.is_empty() {
+        return Err(BombermanError::EmptyFileError);
+    }
+
+    let dimension = rows.len() as u32;
+    let mut map: HashMap<Coordinate, Item> = HashMap::new();
+    for (x, row) in rows.iter().enumerate() {
+        let cells = row.as_array().ok_or(BombermanError::InvalidJson)?;
+        if cells.len() != rows.len() {
+            return Err(BombermanError::NonSquareBoardError);
+        }
+        for (y, cell) in cells.iter().enumerate() {
+            let item = item_from_json(cell)?;
+            if item != Item::Empty {
+                map.insert(Coordinate::new(x as u32, y as u32, dimension - 1), item);
+            }
+        }
+    }
+    Ok(Board::new(map, dimension))
+}
+
+/// Escribe el tablero en el formato comprimido: en cada fila, las casillas iguales consecutivas
+/// se escriben una sola vez, precedidas de la cantidad si es mayor a uno.
+pub fn board_to_run_length(board: &Board) -> String {
+    let mut text = String::new();
+    for x in 0..board.dimension() {
+        let mut runs: Vec<(Item, usize)> = Vec::new();
+        for y in 0..board.dimension() {
+            let item = board.get(x, y);
+            match runs.last_mut() {
+                Some((last, count)) if *last == item => *count += 1,
+                _ => runs.push((item, 1)),
+            }
+        }
+        let cells: Vec<String> = runs
+            .iter()
+            .map(|(item, count)| match count {
+                1 => item.to_string(),
+                count => format!("{}{}", count, item),
+            })
+            .collect();
+        text.push_str(&cells.join(" "));
+        text.push('\n');
+    }
+    text
+}
+
+/// Representa una coordenada como `{"row":x,"column":y}`.
+pub fn coordinate_to_json(coordinate: &Coordinate) -> JsonValue {
+    JsonValue::object(vec![
+        ("row", JsonValue::Number(coordinate.x as f64)),
+        ("column", JsonValue::Number(coordinate.y as f64)),
+    ])
+}
+
+/// Arma el resultado de una ejecucion en JSON: el tablero final y el detalle de la detonacion
+/// (bomba inicial, bombas detonadas en orden y rafagas recibidas por cada casilla).
+pub fn result_to_json(board: &Board, report: &DetonationReport) -> JsonValue {
+    let mut affected: Vec<(&Coordinate, &u32)> = report.affected.iter().collect();
+    affected.sort();
+    let affected = affected
+        .into_iter()
+        .map(|(coordinate, hits)| {
+            JsonValue::object(vec![
+                ("row", JsonValue::Number(coordinate.x as f64)),
+                ("column", JsonValue::Number(coordinate.y as f64)),
+                ("hits", JsonValue::Number(*hits as f64)),
+            ])
+        })
+        .collect();
+
+    let bomb = match report.chain.first() {
+        Some(bomb) => coordinate_to_json(bomb),
+        None => JsonValue::Null,
+    };
+    JsonValue::object(vec![
+        ("board", board_to_json(board)),
+        (
+            "detonation",
+            JsonValue::object(vec![
+                ("bomb", bomb),
+                (
+                    "chain",
+                    JsonValue::Array(report.chain.iter().map(coordinate_to_json).collect()),
+                ),
+                ("affected", JsonValue::Array(affected)),
+            ]),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_round_trip() {
+        for token in ["F2", "B3", "S1", "R", "W", "DL"] {
+            let item = Item::new(token).unwrap();
+            assert_eq!(item_from_json(&item_to_json(&item)), Ok(item));
+        }
+    }
+
+    #[test]
+    fn test_line_format_from_name() {
+        assert_eq!(LineFormat::from_name("json"), Ok(LineFormat::Json));
+        assert_eq!(
+            LineFormat::from_name("xml"),
+            Err(BombermanError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn test_item_from_json_errors() {
+        let parse = |text: &str| item_from_json(&json::parse(text).unwrap());
+        assert_eq!(
+            parse(r#"{"type":"dragon"}"#),
+            Err(BombermanError::InvalidItem)
+        );
+        assert_eq!(
+            parse(r#"{"type":"enemy","life":7}"#),
+            Err(BombermanError::InvalidEnemyLife)
+        );
+        assert_eq!(
+            parse(r#"{"type":"normal_bomb"}"#),
+            Err(BombermanError::InvalidNormalBombFormat)
+        );
+        assert_eq!(
+            parse(r#"{"type":"deflection","direction":"X"}"#),
+            Err(BombermanError::InvalidDeflectionFormat)
+        );
+    }
+
+    #[test]
+    fn test_board_from_json() {
+        let text = r#"{"board":[[{"type":"normal_bomb","range":1},{"type":"empty"}],
+                                [{"type":"empty"},{"type":"wall"}]]}"#;
+        let board = board_from_json(text).unwrap();
+        assert_eq!(board.to_string(), "B1 _\n_ W\n");
+    }
+
+    #[test]
+    fn test_run_length_round_trip() {
+        let game = crate::game::Game::from_file("./tests/inputs/catedra_2.txt").unwrap();
+        let text = board_to_run_length(game.board());
+        assert!(text.starts_with("2_ B2 _ B1 2_\n_ W _ W _ W _\n"));
+        let decoded = crate::game::Game::from_text(&text).unwrap();
+        assert_eq!(decoded.board(), game.board());
+    }
+
+    #[test]
+    fn test_board_from_json_non_square() {
+        let text = r#"[[{"type":"empty"},{"type":"empty"}]]"#;
+        assert_eq!(
+            board_from_json(text),
+            Err(BombermanError::NonSquareBoardError)
+        );
+    }
+}
@@ -1,99 +1,228 @@
 use std::any::Any;
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::io::{BufWriter, Write};
+use std::sync::Arc;
 
-use crate::constants::{DOWN, EMPTY_SQUARE, LEFT, RIGHT, UP};
-use crate::coordinate::{self, Coordinate};
-use crate::file_io;
-use crate::item::Item;
+use crate::board::Board;
+use crate::coordinate::Coordinate;
+use crate::detonation::{self, DetonationReport};
+use crate::file_io::{self, InputWarning};
+use crate::header::{Header, Rule};
+use crate::item::{self, Item};
 use crate::{config::Config, error::BombermanError};
 
-type Displacement = fn(&Coordinate) -> Option<Coordinate>;
-type Expansion = fn(&HashMap<Coordinate, Item>, &Coordinate, Displacement) -> Option<Displacement>;
-
-#[derive(Debug)]
+/// Estado del juego: el tablero leido del archivo de entrada, su encabezado y las advertencias
+/// de la normalizacion del archivo.
+///
+/// El tablero se comparte entre las copias del juego y solo se duplica cuando una de ellas lo
+/// modifica, por lo que clonar un `Game` para probar distintas detonaciones es barato.
+#[derive(Debug, Clone)]
 pub struct Game {
-    map: HashMap<Coordinate, Item>,
-    map_dimension: u32,
+    board: Arc<Board>,
+    header: Header,
+    warnings: Vec<InputWarning>,
 }
 
 impl Game {
+    /// Crea un juego a partir del archivo de entrada de la configuracion. Con `strict` el archivo
+    /// se lee sin normalizar (ver `from_text_strict`). Las reglas de la configuracion se agregan a
+    /// las del encabezado.
     pub fn new(conf: &Config) -> Result<Game, BombermanError> {
+        let game = if conf.strict {
+            Game::from_text_strict(&file_io::read_to_string(&conf.name_input)?)?
+        } else {
+            Game::from_file(&conf.name_input)?
+        };
+        Ok(game.with_rules(&conf.rules))
+    }
+
+    /// Crea un juego a partir del tablero guardado en `path` (o `-` para leerlo de la entrada estándar).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve los mismos errores que la lectura del archivo y el parseo de los items.
+    pub fn from_file(path: &str) -> Result<Game, BombermanError> {
+        let content = file_io::read_to_string(path)?;
+        Game::from_text(&content)
+    }
+
+    /// Crea un juego a partir del texto de un tablero, con el mismo formato que los archivos de entrada.
+    ///
+    /// El texto se normaliza antes de leerlo (ver `file_io::normalize`); las correcciones quedan
+    /// disponibles en `warnings`.
+    ///
+    /// # Errores
+    ///
+    /// Los mismos que `from_text_strict`.
+    pub fn from_text(content: &str) -> Result<Game, BombermanError> {
+        let normalized = file_io::normalize(content);
+        let mut game = Game::from_text_strict(&normalized.text)?;
+        game.warnings = normalized.warnings;
+        Ok(game)
+    }
+
+    /// Crea un juego a partir del texto de un tablero sin normalizarlo: por ejemplo, una linea
+    /// vacia al final se cuenta como una fila del tablero.
+    ///
+    /// Las lineas de comentario se ignoran, salvo las del encabezado (ver `Header`). El tablero
+    /// tambien puede estar en formato comprimido (ver `file_io::board_text`).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::NonSquareBoardError` si el tablero no es cuadrado,
+    /// `BombermanError::InvalidHeader` si el encabezado no es valido y los errores del parseo de
+    /// los items.
+    pub fn from_text_strict(content: &str) -> Result<Game, BombermanError> {
+        let content = &file_io::board_text(content)?;
+        let header = Header::parse(content)?;
         let mut map: HashMap<Coordinate, Item> = HashMap::new();
-        let map_dimension = match file_io::get_matrix_dimensions(&conf.name_input)? {
+        let map_dimension = match file_io::matrix_dimensions(content)? {
             Some(dimension) => dimension as u32,
             None => return Err(BombermanError::NonSquareBoardError),
         };
-        file_io::read_input(&conf.name_input, map_dimension, process_line, &mut map)?;
+        file_io::process_input(content, map_dimension, process_line, &mut map)?;
 
-        Ok(Game { map, map_dimension })
+        let mut game = Game::from_board(Board::new(map, map_dimension));
+        game.header = header;
+        Ok(game)
     }
 
-    pub fn denotate_bomb(&mut self, x: u32, y: u32) -> Result<(), BombermanError> {
-        // let map_rc = Rc::new(RefCell::new(self.map.clone()));
-        let bomb_detonate = Coordinate::new(x, y, self.map_dimension - 1);
+    /// Crea un juego a partir de un tablero ya construido, sin encabezado.
+    pub fn from_board(board: Board) -> Game {
+        Game {
+            board: Arc::new(board),
+            header: Header::default(),
+            warnings: Vec::new(),
+        }
+    }
 
-        let affected = match self.map.get(&bomb_detonate) {
-            Some(Item::NormalBomb(range)) => {
-                detonate_explosion_2(&self.map, &bomb_detonate, *range, normal_bomb_effect)
-            }
-            Some(Item::TransferBomb(range)) => {
-                detonate_explosion_2(&self.map, &bomb_detonate, *range, normal_transfer_effect)
+    /// Agrega `rules` a las reglas del encabezado del juego, si no las tenia.
+    pub fn with_rules(mut self, rules: &[Rule]) -> Game {
+        for rule in rules {
+            if !self.header.has_rule(*rule) {
+                self.header.rules.push(*rule);
             }
-            _ => return Err(BombermanError::InvalidBombCoordinate),
-        };
-        self.update_damage(&affected);
+        }
+        self
+    }
+
+    /// Devuelve las correcciones que se aplicaron al normalizar el archivo de entrada.
+    pub fn warnings(&self) -> &[InputWarning] {
+        &self.warnings
+    }
+
+    /// Devuelve el encabezado del archivo del que se leyo el juego.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Devuelve el tablero actual del juego.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Detona la bomba en la fila `x` y columna `y` y deja en el juego el tablero resultante.
+    /// Devuelve el detalle de la cadena de explosiones.
+    ///
+    /// # Errores
+    ///
+    /// Los mismos que `simulate`.
+    pub fn denotate_bomb(&mut self, x: u32, y: u32) -> Result<DetonationReport, BombermanError> {
+        let (board, report) = self.simulate(x, y)?;
+        self.board = Arc::new(board);
+        Ok(report)
+    }
+
+    /// Reemplaza el item de la fila `x` y columna `y` por `item`.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidCoordinate` si la casilla esta fuera del tablero.
+    pub fn set(&mut self, x: u32, y: u32, item: Item) -> Result<(), BombermanError> {
+        let coordinate = self.cell(x, y)?;
+        Arc::make_mut(&mut self.board).set(coordinate, item);
         Ok(())
     }
 
-    pub fn update_damage(&mut self, affected: &HashMap<Coordinate, u32>) {
-        for (coordinate, damage) in affected {
-            match self.map.get(coordinate) {
-                Some(Item::Enemy(life)) => {
-                    if *life <= *damage {
-                        self.map.remove(coordinate);
-                    } else {
-                        self.map.insert(*coordinate, Item::Enemy(life - damage));
-                    }
-                }
-                Some(Item::NormalBomb(_)) => {
-                    self.map.remove(coordinate);
-                }
-                Some(Item::TransferBomb(_)) => {
-                    self.map.remove(coordinate);
-                }
-                _ => (),
-            }
+    /// Devuelve la coordenada de la fila `x` y columna `y`.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidCoordinate` si la casilla esta fuera del tablero.
+    pub fn cell(&self, x: u32, y: u32) -> Result<Coordinate, BombermanError> {
+        if x >= self.dimension() || y >= self.dimension() {
+            return Err(BombermanError::InvalidCoordinate);
         }
+        Ok(self.board.coordinate(x, y))
     }
 
-    pub fn save_game(&self, path: &str) -> Result<(), BombermanError> {
-        let file = file_io::open_file_for_writing(path)?;
-        let mut writer = BufWriter::new(file);
-        for x in 0..self.map_dimension {
-            for y in 0..self.map_dimension {
-                let key = Coordinate::new(x, y, self.map_dimension - 1);
-                let value = self.map.get(&key).unwrap_or(&Item::Empty);
-                write_item(&mut writer, value)?;
-                if y != self.map_dimension - 1 {
-                    let _ = writer.write_all(b" ");
-                }
-            }
-            let _ = writer.write_all(b"\n");
+    /// Simula la detonacion de la bomba en la fila `x` y columna `y` sin modificar el juego.
+    ///
+    /// Devuelve el tablero que quedaria luego de la detonacion junto con el detalle de la cadena
+    /// de explosiones. Permite hacer varias consultas sobre el mismo juego sin volver a leer el archivo.
+    ///
+    /// Si el encabezado incluye la regla `destroy-rocks`, las rocas alcanzadas se destruyen.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidBombCoordinate` si en la coordenada no hay una bomba.
+    pub fn simulate(&self, x: u32, y: u32) -> Result<(Board, DetonationReport), BombermanError> {
+        let bomb = self.board.coordinate(x, y);
+        let report = match detonation::detonate(&self.board, &bomb) {
+            Some(report) => report,
+            None => return Err(BombermanError::InvalidBombCoordinate),
+        };
+        let mut board = (*self.board).clone();
+        detonation::apply_damage(&mut board, &report.affected);
+        if self.header.has_rule(Rule::DestroyRocks) {
+            detonation::destroy_rocks(&mut board, &report.affected);
         }
-        Ok(())
+        Ok((board, report))
+    }
+
+    /// Calcula, sin modificar el tablero, las casillas alcanzadas por la cadena de explosiones
+    /// que se inicia al detonar la bomba en `bomb`, junto con la cantidad de rafagas que recibe cada una.
+    ///
+    /// Devuelve `None` si en la coordenada no hay una bomba.
+    pub fn blast_reach(&self, bomb: &Coordinate) -> Option<HashMap<Coordinate, u32>> {
+        detonation::detonate(&self.board, bomb).map(|report| report.affected)
     }
-}
 
-fn write_item(writer: &mut BufWriter<std::fs::File>, value: &Item) -> Result<(), BombermanError> {
-    match writer.write_all(format!("{}", value).as_bytes()) {
-        Ok(_) => (),
-        Err(_) => return Err(BombermanError::Write),
-    };
-    match writer.flush() {
-        Ok(_) => Ok(()),
-        Err(_) => Err(BombermanError::Write),
+    /// Devuelve la dimension (cantidad de filas y columnas) del tablero.
+    pub fn dimension(&self) -> u32 {
+        self.board.dimension()
+    }
+
+    /// Devuelve el item que se encuentra en la fila `x` y columna `y`, o `Item::Empty` si la casilla esta vacia.
+    pub fn get(&self, x: u32, y: u32) -> Item {
+        self.board.get(x, y)
+    }
+
+    /// Devuelve las coordenadas de todas las bombas del tablero, ordenadas por fila y columna.
+    pub fn bombs(&self) -> Vec<Coordinate> {
+        self.board
+            .coordinates_where(|item| matches!(item, Item::NormalBomb(_) | Item::TransferBomb(_)))
+    }
+
+    /// Devuelve las coordenadas de todos los enemigos del tablero, ordenadas por fila y columna.
+    pub fn enemies(&self) -> Vec<Coordinate> {
+        self.board
+            .coordinates_where(|item| matches!(item, Item::Enemy(_)))
+    }
+
+    /// Devuelve las coordenadas de todos los desvios del tablero, ordenadas por fila y columna.
+    pub fn deflections(&self) -> Vec<Coordinate> {
+        self.board
+            .coordinates_where(|item| matches!(item, Item::Deflection(_)))
+    }
+
+    pub fn update_damage(&mut self, affected: &HashMap<Coordinate, u32>) {
+        detonation::apply_damage(Arc::make_mut(&mut self.board), affected);
+    }
+
+    /// Guarda el tablero en `path`, con el mismo formato que el archivo de entrada. Si la ruta es
+    /// `-`, lo escribe en la salida estándar.
+    pub fn save_game(&self, path: &str) -> Result<(), BombermanError> {
+        file_io::write_output(path, &self.board.to_string())
     }
 }
 
@@ -106,6 +235,12 @@ fn process_generic_ptr(
     }
 }
 
+/// Procesa una fila del archivo de entrada e inserta sus items en el mapa apuntado por `ptr`.
+///
+/// # Errores
+///
+/// Si algun item no se puede parsear, devuelve `BombermanError::InvalidToken` con el error del
+/// item, su ubicacion (linea y columna, empezando en 1) y una sugerencia si se encontro alguna.
 pub fn process_line(
     line: &str,
     row: usize,
@@ -114,172 +249,24 @@ pub fn process_line(
 ) -> Result<(), BombermanError> {
     let hash: &mut HashMap<Coordinate, Item> = process_generic_ptr(ptr)?;
 
-    let words = line.split_whitespace();
-
-    for (y, s) in words.enumerate() {
-        if s == EMPTY_SQUARE {
-            continue;
+    for (y, item) in parse_line(line, row).into_iter().enumerate() {
+        match item? {
+            Item::Empty => (),
+            item => {
+                hash.insert(Coordinate::new(row as u32, y as u32, max_value - 1), item);
+            }
         }
-        hash.insert(
-            Coordinate::new(row as u32, y as u32, max_value - 1),
-            Item::new(s)?,
-        );
     }
     Ok(())
 }
 
-fn normal_bomb_effect(
-    map: &HashMap<Coordinate, Item>,
-    coordinate: &Coordinate,
-    f: Displacement,
-) -> Option<Displacement> {
-    match map.get(coordinate).unwrap_or(&Item::Empty) {
-        Item::Rock | Item::Wall => None,
-        Item::Deflection(UP) => Some(coordinate::Coordinate::up),
-        Item::Deflection(DOWN) => Some(coordinate::Coordinate::down),
-        Item::Deflection(LEFT) => Some(coordinate::Coordinate::left),
-        Item::Deflection(RIGHT) => Some(coordinate::Coordinate::right),
-        _ => Some(f),
-    }
-}
-
-fn normal_transfer_effect(
-    map: &HashMap<Coordinate, Item>,
-    coordinate: &Coordinate,
-    f: Displacement,
-) -> Option<Displacement> {
-    match map.get(coordinate).unwrap_or(&Item::Empty) {
-        Item::Wall => None,
-        Item::Deflection(UP) => Some(coordinate::Coordinate::up),
-        Item::Deflection(DOWN) => Some(coordinate::Coordinate::down),
-        Item::Deflection(LEFT) => Some(coordinate::Coordinate::left),
-        Item::Deflection(RIGHT) => Some(coordinate::Coordinate::right),
-        _ => Some(f),
-    }
-}
-fn detonate_explosion_2(
-    map: &HashMap<Coordinate, Item>,
-    bomb: &Coordinate,
-    range: u32,
-    g: Expansion,
-) -> HashMap<Coordinate, u32> {
-    let mut affected: HashMap<Coordinate, u32> = HashMap::new();
-    let mut detonated_bombs: HashSet<Coordinate> = HashSet::new();
-    detonate_explosion(map, &mut affected, bomb, range, &mut detonated_bombs, g);
-    affected
-}
-
-fn detonate_explosion(
-    map: &HashMap<Coordinate, Item>,
-    affected: &mut HashMap<Coordinate, u32>,
-    bomb: &Coordinate,
-    range: u32,
-    detonated_bombs: &mut HashSet<Coordinate>,
-    g: Expansion,
-) {
-    if range == 0 {
-        return;
-    }
-
-    // let mut affected: HashMap<Coordinate, u32> = HashMap::new();
-    detonated_bombs.insert(*bomb);
-
-    expansive_wave(
-        map,
-        affected,
-        bomb,
-        range + 1,
-        detonated_bombs,
-        coordinate::Coordinate::right,
-        g,
-    );
-    expansive_wave(
-        map,
-        affected,
-        bomb,
-        range + 1,
-        detonated_bombs,
-        coordinate::Coordinate::left,
-        g,
-    );
-    expansive_wave(
-        map,
-        affected,
-        bomb,
-        range + 1,
-        detonated_bombs,
-        coordinate::Coordinate::up,
-        g,
-    );
-    expansive_wave(
-        map,
-        affected,
-        bomb,
-        range + 1,
-        detonated_bombs,
-        coordinate::Coordinate::down,
-        g,
-    );
-    // *affected
-}
-
-fn expansive_wave(
-    map: &HashMap<Coordinate, Item>,
-    affected: &mut HashMap<Coordinate, u32>,
-    coordinate: &Coordinate,
-    range: u32,
-    detonated_bombs: &mut HashSet<Coordinate>,
-    f: Displacement,
-    g: Expansion,
-) {
-    if range == 0 {
-        return;
-    }
-    *affected.entry(*coordinate).or_insert(0) += 1;
-
-    match map.get(coordinate).unwrap_or(&Item::Empty) {
-        Item::NormalBomb(r) => {
-            if !detonated_bombs.contains(coordinate) {
-                detonate_explosion(
-                    map,
-                    affected,
-                    coordinate,
-                    *r,
-                    detonated_bombs,
-                    normal_bomb_effect,
-                );
-            };
-        }
-        Item::TransferBomb(r) => {
-            if !detonated_bombs.contains(coordinate) {
-                detonate_explosion(
-                    map,
-                    affected,
-                    coordinate,
-                    *r,
-                    detonated_bombs,
-                    normal_transfer_effect,
-                );
-            };
-        }
-        _ => (),
-    };
-
-    let f = match g(map, coordinate, f) {
-        Some(f) => f,
-        None => return,
-    };
-    let prox_coordinate = match f(coordinate) {
-        Some(coordinate) => coordinate,
-        None => return,
-    };
-    expansive_wave(
-        map,
-        affected,
-        &prox_coordinate,
-        range - 1,
-        detonated_bombs,
-        f,
-        g,
-    )
+/// Parsea todos los items de una fila, sin detenerse en el primero invalido.
+///
+/// Devuelve un resultado por casilla, en orden. Las casillas vacias se devuelven como
+/// `Item::Empty` y los items invalidos como `BombermanError::InvalidToken` con su ubicacion.
+pub fn parse_line(line: &str, row: usize) -> Vec<Result<Item, BombermanError>> {
+    file_io::tokens_with_columns(line)
+        .into_iter()
+        .map(|(column, s)| item::parse_token(s, row + 1, column))
+        .collect()
 }
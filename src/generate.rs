@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::board::Board;
+use crate::constants::{DOWN, LEFT, MAX_LIFE, RIGHT, UP};
+use crate::coordinate::Coordinate;
+use crate::error::BombermanError;
+use crate::item::Item;
+
+/// Alcance maximo de las bombas generadas.
+const MAX_RANGE: u32 = 3;
+
+/// Menor cantidad de filas que se acepta para generar un tablero desde la linea de comandos.
+pub const MIN_SIZE: u32 = 2;
+
+/// Mayor cantidad de filas que se acepta para generar un tablero desde la linea de comandos.
+pub const MAX_SIZE: u32 = 1000;
+
+/// Parametros para generar un tablero.
+///
+/// # Campos
+///
+/// * `size`: Cantidad de filas (y de columnas) del tablero.
+/// * `seed`: Semilla del generador; la misma semilla y los mismos parametros generan siempre el
+///   mismo tablero.
+/// * `bombs`: Cantidad de bombas. Una de cada cuatro, en promedio, es de traspaso.
+/// * `enemies`: Cantidad de enemigos, con vida entre 1 y 3.
+/// * `rocks`: Cantidad de rocas.
+/// * `deflections`: Cantidad de desvios.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub size: u32,
+    pub seed: u64,
+    pub bombs: usize,
+    pub enemies: usize,
+    pub rocks: usize,
+    pub deflections: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            size: 7,
+            seed: 0,
+            bombs: 2,
+            enemies: 3,
+            rocks: 4,
+            deflections: 1,
+        }
+    }
+}
+
+/// Generador pseudoaleatorio xorshift64*, suficiente para armar tableros reproducibles.
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Random {
+        // splitmix64 para que semillas parecidas (o cero) den secuencias distintas.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Random((z ^ (z >> 31)) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Devuelve un numero entre 0 y `n - 1`.
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// Genera un tablero al azar.
+///
+/// Las paredes se ubican como en los tableros de ejemplo, en las casillas de fila y columna
+/// impares. El resto de los items se reparten al azar entre las casillas libres.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::TooManyItems` si los items pedidos no entran en las casillas libres.
+pub fn generate(options: &Options) -> Result<Board, BombermanError> {
+    let max = options.size.saturating_sub(1);
+    let mut map = HashMap::new();
+    let mut free = Vec::new();
+    for x in 0..options.size {
+        for y in 0..options.size {
+            let coordinate = Coordinate::new(x, y, max);
+            if x % 2 == 1 && y % 2 == 1 {
+                map.insert(coordinate, Item::Wall);
+            } else {
+                free.push(coordinate);
+            }
+        }
+    }
+    [
+        options.bombs,
+        options.enemies,
+        options.rocks,
+        options.deflections,
+    ]
+    .into_iter()
+    .try_fold(0, usize::checked_add)
+    .filter(|wanted| *wanted <= free.len())
+    .ok_or(BombermanError::TooManyItems)?;
+
+    let mut random = Random::new(options.seed);
+    for i in (1..free.len()).rev() {
+        free.swap(i, random.below(i as u64 + 1) as usize);
+    }
+    let mut cells = free.into_iter();
+    for coordinate in cells.by_ref().take(options.bombs) {
+        let range = 1 + random.below(MAX_RANGE as u64) as u32;
+        let bomb = match random.below(4) {
+            0 => Item::TransferBomb(range),
+            _ => Item::NormalBomb(range),
+        };
+        map.insert(coordinate, bomb);
+    }
+    for coordinate in cells.by_ref().take(options.enemies) {
+        let life = 1 + random.below(MAX_LIFE as u64) as u32;
+        map.insert(coordinate, Item::Enemy(life));
+    }
+    for coordinate in cells.by_ref().take(options.rocks) {
+        map.insert(coordinate, Item::Rock);
+    }
+    for coordinate in cells.by_ref().take(options.deflections) {
+        let direction = [LEFT, RIGHT, UP, DOWN][random.below(4) as usize];
+        map.insert(coordinate, Item::Deflection(direction));
+    }
+    Ok(Board::new(map, options.size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_is_reproducible() {
+        let options = Options {
+            seed: 42,
+            ..Options::default()
+        };
+        let board = generate(&options).unwrap();
+        assert_eq!(board, generate(&options).unwrap());
+        assert_eq!(board.dimension(), 7);
+        assert_eq!(board.get(1, 1), Item::Wall);
+        assert_eq!(
+            board
+                .coordinates_where(|i| matches!(i, Item::Enemy(_)))
+                .len(),
+            3
+        );
+        assert_eq!(
+            board
+                .coordinates_where(|i| matches!(i, Item::NormalBomb(_) | Item::TransferBomb(_)))
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_generate_too_many_items() {
+        let options = Options {
+            size: 2,
+            ..Options::default()
+        };
+        assert_eq!(generate(&options), Err(BombermanError::TooManyItems));
+
+        let options = Options {
+            bombs: usize::MAX,
+            enemies: 5,
+            ..Options::default()
+        };
+        assert_eq!(generate(&options), Err(BombermanError::TooManyItems));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::config;
+use crate::error::BombermanError;
+use crate::file_io;
+
+const TITLE_KEY: &str = "title";
+const AUTHOR_KEY: &str = "author";
+const DETONATE_KEY: &str = "detonate";
+const RULES_KEY: &str = "rules";
+
+/// Reglas opcionales que modifican el resultado de una detonacion.
+///
+/// # Variantes
+///
+/// - `DestroyRocks`: Las rocas alcanzadas por alguna rafaga se destruyen. Igual bloquean la
+///   rafaga de las bombas normales que las alcanzan.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    DestroyRocks,
+}
+
+impl Rule {
+    /// Obtiene la regla a partir de su nombre (`destroy-rocks`).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidHeader` si el nombre no corresponde a ninguna regla.
+    pub fn from_name(name: &str) -> Result<Rule, BombermanError> {
+        match name {
+            "destroy-rocks" => Ok(Rule::DestroyRocks),
+            _ => Err(BombermanError::InvalidHeader),
+        }
+    }
+}
+
+/// Datos opcionales del encabezado de un archivo de tablero.
+///
+/// El encabezado son las lineas de comentario (que empiezan con `#`) anteriores al tablero con
+/// el formato `# clave: valor`. Las claves desconocidas se consideran comentarios comunes.
+///
+/// # Campos
+///
+/// * `title`: Titulo del tablero (`# title: ...`).
+/// * `author`: Autor del tablero (`# author: ...`).
+/// * `detonate`: Fila y columna de la bomba a detonar (`# detonate: y x`, en el mismo orden que
+///   los argumentos de linea de comandos: primero la columna y despues la fila).
+/// * `rules`: Reglas opcionales del tablero (`# rules: destroy-rocks`), separadas por comas o espacios.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Header {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub detonate: Option<(usize, usize)>,
+    pub rules: Vec<Rule>,
+}
+
+impl Header {
+    /// Lee el encabezado del texto de un tablero. Si el texto no tiene encabezado, devuelve uno vacio.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidHeader` si `detonate` no tiene dos enteros no negativos o
+    /// si `rules` incluye una regla desconocida.
+    pub fn parse(content: &str) -> Result<Header, BombermanError> {
+        let mut header = Header::default();
+        for line in content.lines() {
+            if !file_io::is_comment(line) {
+                break;
+            }
+            let (key, value) = match line.trim_start()[1..].split_once(':') {
+                Some((key, value)) => (key.trim().to_lowercase(), value.trim()),
+                None => continue,
+            };
+            match key.as_str() {
+                TITLE_KEY => header.title = Some(value.to_string()),
+                AUTHOR_KEY => header.author = Some(value.to_string()),
+                DETONATE_KEY => header.detonate = Some(parse_detonate(value)?),
+                RULES_KEY => {
+                    for name in value.split(|c: char| c == ',' || c.is_whitespace()) {
+                        if !name.is_empty() {
+                            header.rules.push(Rule::from_name(name)?);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(header)
+    }
+
+    /// Indica si el tablero usa la regla `rule`.
+    pub fn has_rule(&self, rule: Rule) -> bool {
+        self.rules.contains(&rule)
+    }
+}
+
+/// Lee `y x` y devuelve la coordenada como `(x, y)`.
+fn parse_detonate(value: &str) -> Result<(usize, usize), BombermanError> {
+    let values: Vec<&str> = value.split_whitespace().collect();
+    if values.len() != 2 {
+        return Err(BombermanError::InvalidHeader);
+    }
+    match (
+        config::get_coordinate(values[0]),
+        config::get_coordinate(values[1]),
+    ) {
+        (Ok(y), Ok(x)) => Ok((x, y)),
+        _ => Err(BombermanError::InvalidHeader),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        let content = "# title: Catedra 2\n# author: Ana\n# un comentario\n# detonate: 2 4\n# rules: destroy-rocks\n_ _\n_ _\n";
+        let header = Header::parse(content).unwrap();
+        assert_eq!(header.title.as_deref(), Some("Catedra 2"));
+        assert_eq!(header.author.as_deref(), Some("Ana"));
+        assert_eq!(header.detonate, Some((4, 2)));
+        assert!(header.has_rule(Rule::DestroyRocks));
+    }
+
+    #[test]
+    fn test_parse_without_header() {
+        assert_eq!(Header::parse("B1 _\n_ _\n"), Ok(Header::default()));
+    }
+
+    #[test]
+    fn test_parse_invalid_header() {
+        assert_eq!(
+            Header::parse("# detonate: 2\n"),
+            Err(BombermanError::InvalidHeader)
+        );
+        assert_eq!(
+            Header::parse("# rules: fly\n"),
+            Err(BombermanError::InvalidHeader)
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::error::BombermanError;
+
+/// Idioma elegido para los mensajes del proceso. Empieza en español.
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::Es as u8);
+
+/// Idioma de los mensajes del programa: errores, ayuda y reportes.
+///
+/// # Variantes
+///
+/// - `Es`: Español, el idioma por defecto.
+/// - `En`: Inglés.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Lang {
+    #[default]
+    Es,
+    En,
+}
+
+impl Lang {
+    /// Obtiene el idioma a partir de su nombre (`es` o `en`).
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidLang` si el nombre no corresponde a ningún idioma.
+    pub fn from_name(name: &str) -> Result<Lang, BombermanError> {
+        match name.to_lowercase().as_str() {
+            "es" => Ok(Lang::Es),
+            "en" => Ok(Lang::En),
+            _ => Err(BombermanError::InvalidLang),
+        }
+    }
+
+    /// Obtiene el idioma a partir de una configuración regional como la de la variable `LANG`
+    /// (`en_US.UTF-8`, `es_AR`, ...). Devuelve `None` si no corresponde a ningún idioma conocido.
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let name = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        Lang::from_name(name).ok()
+    }
+
+    /// Elige el idioma: el indicado con `--lang` si lo hay, si no el de la variable `LANG` y
+    /// si ninguno corresponde a un idioma conocido, español.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidLang` si `--lang` indica un idioma desconocido.
+    pub fn resolve(flag: Option<&str>, locale: Option<&str>) -> Result<Lang, BombermanError> {
+        match flag {
+            Some(name) => Lang::from_name(name),
+            None => Ok(locale.and_then(Lang::from_locale).unwrap_or_default()),
+        }
+    }
+
+    /// Devuelve el idioma elegido para los mensajes del proceso.
+    pub fn current() -> Lang {
+        match CURRENT.load(Ordering::Relaxed) {
+            1 => Lang::En,
+            _ => Lang::Es,
+        }
+    }
+
+    /// Elige el idioma de los mensajes de todo el proceso.
+    pub fn set_current(lang: Lang) {
+        CURRENT.store(lang as u8, Ordering::Relaxed);
+    }
+}
+
+/// Mensaje del catalogo, con su texto en cada idioma.
+///
+/// Los textos pueden tener marcas `{}` que `fill` reemplaza, en orden, por sus argumentos.
+///
+/// # Campos
+///
+/// * `es`: Texto en español.
+/// * `en`: Texto en inglés.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Message {
+    pub es: &'static str,
+    pub en: &'static str,
+}
+
+impl Message {
+    /// Devuelve el texto en el idioma elegido para el proceso.
+    pub fn text(&self) -> &'static str {
+        self.text_in(Lang::current())
+    }
+
+    /// Devuelve el texto en el idioma `lang`.
+    pub fn text_in(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::Es => self.es,
+            Lang::En => self.en,
+        }
+    }
+
+    /// Devuelve el texto en el idioma elegido para el proceso, con cada `{}` reemplazado por el
+    /// argumento correspondiente.
+    pub fn fill(&self, args: &[&dyn Display]) -> String {
+        self.fill_in(Lang::current(), args)
+    }
+
+    /// Igual que `fill`, en el idioma `lang`.
+    pub fn fill_in(&self, lang: Lang, args: &[&dyn Display]) -> String {
+        let mut parts = self.text_in(lang).split("{}");
+        let mut text = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            if let Some(arg) = args.get(i) {
+                text.push_str(&arg.to_string());
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+pub const WARNING: Message = Message {
+    es: "Advertencia: {}",
+    en: "Warning: {}",
+};
+pub const USAGE: Message = Message {
+    es: "Uso: {}",
+    en: "Usage: {}",
+};
+
+// Ayuda de la linea de comandos.
+pub const HELP_OPTIONS: Message = Message {
+    es: "Opciones:",
+    en: "Options:",
+};
+pub const HELP_FLAG: Message = Message {
+    es: "Muestra esta ayuda",
+    en: "Shows this help",
+};
+pub const HELP_OPTIONS_PLACEHOLDER: Message = Message {
+    es: "[opciones]",
+    en: "[options]",
+};
+pub const HELP_OVERVIEW: Message = Message {
+    es: "Uso: {} <comando> [argumentos] [opciones]",
+    en: "Usage: {} <command> [arguments] [options]",
+};
+pub const HELP_COMMANDS: Message = Message {
+    es: "Comandos:",
+    en: "Commands:",
+};
+pub const HELP_GLOBAL_OPTIONS: Message = Message {
+    es: "Opciones globales:",
+    en: "Global options:",
+};
+pub const HELP_HINT: Message = Message {
+    es: "Use '{} <comando> {}' para ver los argumentos y opciones de cada comando.",
+    en: "Run '{} <command> {}' to see the arguments and options of each command.",
+};
+
+// Correcciones del archivo de entrada.
+pub const INPUT_BOM: Message = Message {
+    es: "se quito la marca de orden de bytes (BOM) del inicio del archivo",
+    en: "removed the byte order mark (BOM) at the start of the file",
+};
+pub const INPUT_CRLF: Message = Message {
+    es: "se convirtieron {} fin(es) de linea de Windows (CRLF) a LF",
+    en: "converted {} Windows line ending(s) (CRLF) to LF",
+};
+pub const INPUT_TABS: Message = Message {
+    es: "se reemplazaron tabulaciones por espacios en la(s) linea(s) {}",
+    en: "replaced tabs with spaces on line(s) {}",
+};
+pub const INPUT_TRAILING_LINES: Message = Message {
+    es: "se ignoraron {} linea(s) vacia(s) al final del archivo",
+    en: "ignored {} blank line(s) at the end of the file",
+};
+
+// Analisis del tablero (`check`, `explain`, `solve`).
+pub const CHECK_WINNABLE: Message = Message {
+    es: "Todos los enemigos pueden ser derrotados",
+    en: "All enemies can be defeated",
+};
+pub const CHECK_UNREACHABLE: Message = Message {
+    es: "Enemigo en {} inalcanzable: ninguna cadena de bombas lo impacta",
+    en: "Enemy at {} is unreachable: no bomb chain hits it",
+};
+pub const CHECK_UNDERPOWERED: Message = Message {
+    es: "Enemigo en {} con vida {}: recibe como maximo {} rafaga(s)",
+    en: "Enemy at {} with life {}: receives at most {} blast(s)",
+};
+pub const DEFLECTION_LOOP: Message = Message {
+    es: "Desvios en ciclo: {}",
+    en: "Deflections in a loop: {}",
+};
+pub const DEFLECTION_UNREACHABLE: Message = Message {
+    es: "Desvio en {} inalcanzable por cualquier bomba",
+    en: "Deflection at {} is not reached by any bomb",
+};
+pub const DEFLECTION_INTO_WALL: Message = Message {
+    es: "Desvio en {} apunta directamente a una pared",
+    en: "Deflection at {} points straight into a wall",
+};
+pub const ROUTE_HEADER: Message = Message {
+    es: "Bomba en {}: {} impacto(s)",
+    en: "Bomb at {}: {} hit(s)",
+};
+pub const ROUTE_DEFLECTED: Message = Message {
+    es: "{} desvio a {}",
+    en: "{} deflected to {}",
+};
+pub const ROUTE_DETONATES: Message = Message {
+    es: "{} detona {}",
+    en: "{} detonates {}",
+};
+pub const EXPLAIN_NONE: Message = Message {
+    es: "Ninguna bomba alcanza {}",
+    en: "No bomb reaches {}",
+};
+pub const SOLVE_BOMB: Message = Message {
+    es: "Bomba en {}: derrota {} de {} enemigo(s), quita {} de vida",
+    en: "Bomb at {}: defeats {} of {} enemy(ies), removes {} life",
+};
+pub const SOLVE_ALL: Message = Message {
+    es: "La bomba en {} derrota a todos los enemigos",
+    en: "The bomb at {} defeats every enemy",
+};
+pub const SOLVE_NONE: Message = Message {
+    es: "Ninguna bomba derrota a todos los enemigos",
+    en: "No bomb defeats every enemy",
+};
+pub const VALID_BOARD: Message = Message {
+    es: "El tablero es valido",
+    en: "The board is valid",
+};
+
+// Cambios entre el tablero inicial y el final (`diff`).
+pub const DIFF_ENEMY_DAMAGED: Message = Message {
+    es: "{}: enemigo {} -> {} (vida {} -> {})",
+    en: "{}: enemy {} -> {} (life {} -> {})",
+};
+pub const DIFF_ENEMY_DEFEATED: Message = Message {
+    es: "{}: enemigo {} eliminado",
+    en: "{}: enemy {} defeated",
+};
+pub const DIFF_BOMB_USED: Message = Message {
+    es: "{}: bomba {} consumida",
+    en: "{}: bomb {} used",
+};
+
+// Vista de terminal (`--show`).
+pub const VIEW_BEFORE: Message = Message {
+    es: "Inicial",
+    en: "Before",
+};
+pub const VIEW_AFTER: Message = Message {
+    es: "Final",
+    en: "After",
+};
+pub const VIEW_CHAIN: Message = Message {
+    es: "Cadena: {}",
+    en: "Chain: {}",
+};
+
+// Reportes de `suite` y `batch`.
+pub const SUITE_CASE: Message = Message {
+    es: "# caso {} (linea {})",
+    en: "# case {} (line {})",
+};
+pub const SUITE_RESULT: Message = Message {
+    es: "# resultado: {}",
+    en: "# result: {}",
+};
+pub const SUITE_SUMMARY: Message = Message {
+    es: "{} caso(s): {} ok, {} fallo, {} error, {} sin salida esperada",
+    en: "{} case(s): {} ok, {} failed, {} error, {} without expected output",
+};
+pub const OUTCOME_PASSED: Message = Message { es: "ok", en: "ok" };
+pub const OUTCOME_FAILED: Message = Message {
+    es: "fallo",
+    en: "failed",
+};
+pub const OUTCOME_ERROR: Message = Message {
+    es: "error",
+    en: "error",
+};
+pub const OUTCOME_UNCHECKED: Message = Message {
+    es: "sin salida esperada",
+    en: "no expected output",
+};
+pub const BATCH_CASE: Message = Message {
+    es: "{}: {} (linea {})",
+    en: "{}: {} (line {})",
+};
+pub const BATCH_MISMATCH: Message = Message {
+    es: "la salida no coincide con la esperada",
+    en: "the output does not match the expected one",
+};
+
+// Modo observador (`watch`).
+pub const WATCH_HEADER: Message = Message {
+    es: "Observando {} (Ctrl-C para salir)",
+    en: "Watching {} (Ctrl-C to quit)",
+};
+
+// Consola interactiva (`repl`).
+pub const REPL_WELCOME: Message = Message {
+    es: "Tablero {} cargado. Escriba help para ver los comandos.",
+    en: "Board {} loaded. Type help to list the commands.",
+};
+pub const REPL_HELP: Message = Message {
+    es: "Comandos:
+  show              muestra el tablero
+  detonate x y      detona la bomba en la {}
+  undo              deshace el ultimo cambio
+  set x y item      reemplaza el item en la {} (por ejemplo F2, o _ para vaciarla)
+  rank              lista las bombas de mejor a peor
+  save ruta         guarda el tablero
+  load ruta         carga otro tablero
+  help              muestra esta ayuda
+  quit              sale de la consola",
+    en: "Commands:
+  show              shows the board
+  detonate x y      detonates the bomb at {}
+  undo              undoes the last change
+  set x y item      replaces the item at {} (for example F2, or _ to clear it)
+  rank              lists the bombs from best to worst
+  save path         saves the board
+  load path         loads another board
+  help              shows this help
+  quit              leaves the console",
+};
+pub const REPL_COLUMN_ROW: Message = Message {
+    es: "columna x y la fila y",
+    en: "column x and row y",
+};
+pub const REPL_ROW_COLUMN: Message = Message {
+    es: "fila x y la columna y",
+    en: "row x and column y",
+};
+pub const REPL_SAVED: Message = Message {
+    es: "Tablero guardado en {}",
+    en: "Board saved to {}",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(Lang::resolve(Some("en"), Some("es_AR.UTF-8")), Ok(Lang::En));
+        assert_eq!(Lang::resolve(None, Some("en_US.UTF-8")), Ok(Lang::En));
+        assert_eq!(Lang::resolve(None, Some("C")), Ok(Lang::Es));
+        assert_eq!(Lang::resolve(None, None), Ok(Lang::Es));
+        assert_eq!(
+            Lang::resolve(Some("fr"), None),
+            Err(BombermanError::InvalidLang)
+        );
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(
+            CHECK_UNDERPOWERED.fill_in(Lang::En, &[&"(1, 2)", &3, &1]),
+            "Enemy at (1, 2) with life 3: receives at most 1 blast(s)"
+        );
+        assert_eq!(USAGE.fill_in(Lang::Es, &[&"bomberman"]), "Uso: bomberman");
+    }
+}
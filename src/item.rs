@@ -1,8 +1,9 @@
 use crate::{
     constants::{
-        DEFLECTION, DOWN, ENEMY, LEFT, MAX_LIFE, NORMAL_BOMB, RIGHT, ROCK, TRANSFER_BOMB, UP, WALL,
+        DEFLECTION, DOWN, EMPTY_SQUARE, ENEMY, LEFT, MAX_LIFE, NORMAL_BOMB, RIGHT, ROCK,
+        TRANSFER_BOMB, UP, WALL,
     },
-    error::BombermanError,
+    error::{BombermanError, TokenLocation},
 };
 
 /// Representa los elementos en el mundo del juego Bomberman.
@@ -99,7 +100,7 @@ impl Item {
     /// # Argumentos
     ///
     /// * `s` - La cadena de texto que contiene la vida del enemigo.
-    ///         La cadena debe tener el siguiente formato: `FXXX` donde `XXX` es un número entero no negativo.
+    ///   La cadena debe tener el siguiente formato: `FXXX` donde `XXX` es un número entero no negativo.
     ///
     /// # Errores
     ///
@@ -139,6 +140,72 @@ impl std::fmt::Display for Item {
     }
 }
 
+/// Parsea el item `s` que se encuentra en la linea `line` y la columna `column` del archivo
+/// (ambas empezando en 1). Una casilla vacia se devuelve como `Item::Empty`.
+///
+/// # Errores
+///
+/// Si el item no se puede parsear, devuelve `BombermanError::InvalidToken` con el error del
+/// item, su ubicacion y una sugerencia si se encontro alguna.
+pub fn parse_token(s: &str, line: usize, column: usize) -> Result<Item, BombermanError> {
+    if s == EMPTY_SQUARE {
+        return Ok(Item::Empty);
+    }
+    Item::new(s).map_err(|e| {
+        let location = TokenLocation {
+            line,
+            column,
+            token: s.to_string(),
+            suggestion: suggest(s),
+        };
+        BombermanError::InvalidToken(Box::new(e), location)
+    })
+}
+
+/// Busca un item válido parecido a `s`, para sugerirlo cuando `s` no se puede parsear.
+///
+/// Se prueba, en orden: pasar el texto a mayúsculas, quitarle los caracteres que no son letras ni
+/// números, reemplazar la vida de un enemigo por la más cercana dentro del rango permitido (o
+/// la letra `E` por `F`) y completar con alcance 1 una bomba sin alcance. Un texto sin letras
+/// ni números se sugiere como casilla vacía.
+///
+/// # Argumentos
+///
+/// * `s`: El texto del item que no se pudo parsear.
+///
+/// # Retorno
+///
+/// `Some` con el item sugerido, o `None` si no se encontró ninguno parecido.
+///
+pub fn suggest(s: &str) -> Option<String> {
+    let upper = s.to_uppercase();
+    if upper != s && Item::new(&upper).is_ok() {
+        return Some(upper);
+    }
+    let cleaned: String = upper
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if cleaned.is_empty() {
+        return Some(EMPTY_SQUARE.to_string());
+    }
+    if cleaned != s && Item::new(&cleaned).is_ok() {
+        return Some(cleaned);
+    }
+
+    let mut chars = cleaned.chars();
+    let first = chars.next()?;
+    let rest: String = chars.collect();
+    match first {
+        ENEMY | 'E' => {
+            let life = rest.parse::<u32>().unwrap_or(1).clamp(1, MAX_LIFE);
+            Some(format!("{}{}", ENEMY, life))
+        }
+        NORMAL_BOMB | TRANSFER_BOMB if rest.is_empty() => Some(format!("{}1", first)),
+        _ => None,
+    }
+}
+
 /// Obtiene el valor numérico a partir de una cadena de caracteres `s`.
 ///
 /// Esta función toma una cadena de caracteres `s` y extrae el valor numérico que sigue a la primera letra.
@@ -303,4 +370,16 @@ mod tests {
     fn test_new_item_invalid_3() {
         assert_eq!(Item::new("C183"), Err(BombermanError::InvalidItem));
     }
+
+    #[test]
+    fn test_suggest() {
+        assert_eq!(suggest("f2"), Some("F2".to_string()));
+        assert_eq!(suggest("dl"), Some("DL".to_string()));
+        assert_eq!(suggest("F5"), Some("F3".to_string()));
+        assert_eq!(suggest("E2"), Some("F2".to_string()));
+        assert_eq!(suggest("B"), Some("B1".to_string()));
+        assert_eq!(suggest("W,"), Some("W".to_string()));
+        assert_eq!(suggest("-"), Some("_".to_string()));
+        assert_eq!(suggest("Z"), None);
+    }
 }
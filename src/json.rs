@@ -0,0 +1,329 @@
+use crate::error::BombermanError;
+
+/// Mayor cantidad de listas u objetos anidados que se aceptan al leer un texto JSON. Un tablero
+/// necesita solo unos pocos niveles; el limite evita agotar la pila con textos muy anidados.
+const MAX_DEPTH: usize = 256;
+
+/// Valor JSON.
+///
+/// Implementacion minima para leer y escribir los tableros y resultados en formato JSON sin
+/// depender de bibliotecas externas. Los objetos conservan el orden de sus claves.
+///
+/// # Variantes
+///
+/// - `Null`, `Bool(bool)`, `Number(f64)`, `String(String)`: Valores simples.
+/// - `Array(Vec<JsonValue>)`: Lista de valores.
+/// - `Object(Vec<(String, JsonValue)>)`: Pares clave-valor, en el orden en que fueron leidos o agregados.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Construye un objeto a partir de pares clave-valor.
+    pub fn object(pairs: Vec<(&str, JsonValue)>) -> JsonValue {
+        JsonValue::Object(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Devuelve el valor asociado a `key` si el valor es un objeto que la contiene.
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(pairs) => pairs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Devuelve el contenido si el valor es un string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Devuelve el valor como entero no negativo, si es un numero entero que entra en un `u32`.
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            JsonValue::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64 => {
+                Some(*n as u32)
+            }
+            _ => None,
+        }
+    }
+
+    /// Devuelve los elementos si el valor es una lista.
+    pub fn as_array(&self) -> Option<&Vec<JsonValue>> {
+        match self {
+            JsonValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Escribe el valor en JSON compacto, sin espacios ni saltos de linea.
+impl std::fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => {
+                write!(f, "{}", *n as i64)
+            }
+            JsonValue::Number(n) => write!(f, "{}", n),
+            JsonValue::String(s) => write_string(f, s),
+            JsonValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(pairs) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in pairs.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Lee un texto JSON completo.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InvalidJson` si el texto no es JSON valido, tiene contenido
+/// adicional luego del valor o anida mas de `MAX_DEPTH` listas u objetos.
+pub fn parse(text: &str) -> Result<JsonValue, BombermanError> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.position != parser.chars.len() {
+        return Err(BombermanError::InvalidJson);
+    }
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Result<char, BombermanError> {
+        let c = self.peek().ok_or(BombermanError::InvalidJson)?;
+        self.position += 1;
+        Ok(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), BombermanError> {
+        match self.next()? {
+            c if c == expected => Ok(()),
+            _ => Err(BombermanError::InvalidJson),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, BombermanError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Parser::parse_object),
+            Some('[') => self.nested(Parser::parse_array),
+            Some('"') => Ok(JsonValue::String(self.parse_string()?)),
+            Some('t') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some('f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some('n') => self.parse_literal("null", JsonValue::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(),
+            _ => Err(BombermanError::InvalidJson),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Parser) -> Result<JsonValue, BombermanError>,
+    ) -> Result<JsonValue, BombermanError> {
+        if self.depth == MAX_DEPTH {
+            return Err(BombermanError::InvalidJson);
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_literal(
+        &mut self,
+        literal: &str,
+        value: JsonValue,
+    ) -> Result<JsonValue, BombermanError> {
+        for expected in literal.chars() {
+            self.expect(expected)?;
+        }
+        Ok(value)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, BombermanError> {
+        let start = self.position;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit() || "+-.eE".contains(c)) {
+            self.position += 1;
+        }
+        let number: String = self.chars[start..self.position].iter().collect();
+        match number.parse::<f64>() {
+            Ok(n) => Ok(JsonValue::Number(n)),
+            Err(_) => Err(BombermanError::InvalidJson),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, BombermanError> {
+        self.expect('"')?;
+        let mut s = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Ok(s),
+                '\\' => match self.next()? {
+                    '"' => s.push('"'),
+                    '\\' => s.push('\\'),
+                    '/' => s.push('/'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => {
+                        let mut code = 0;
+                        for _ in 0..4 {
+                            let digit = self
+                                .next()?
+                                .to_digit(16)
+                                .ok_or(BombermanError::InvalidJson)?;
+                            code = code * 16 + digit;
+                        }
+                        s.push(char::from_u32(code).ok_or(BombermanError::InvalidJson)?);
+                    }
+                    _ => return Err(BombermanError::InvalidJson),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, BombermanError> {
+        self.expect('[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                ']' => return Ok(JsonValue::Array(values)),
+                _ => return Err(BombermanError::InvalidJson),
+            }
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, BombermanError> {
+        self.expect('{')?;
+        let mut pairs = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(pairs));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            pairs.push((key, self.parse_value()?));
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                '}' => return Ok(JsonValue::Object(pairs)),
+                _ => return Err(BombermanError::InvalidJson),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_write_round_trip() {
+        let text = r#"{"type":"enemy","life":2,"tags":["a\"b",true,null],"x":-1.5}"#;
+        let value = parse(text).unwrap();
+        assert_eq!(value.get("life").and_then(JsonValue::as_u32), Some(2));
+        assert_eq!(value.to_string(), text);
+    }
+
+    #[test]
+    fn test_parse_whitespace() {
+        let value = parse(" [ [ 1 , 2 ] ,\n [] ] ").unwrap();
+        assert_eq!(value.to_string(), "[[1,2],[]]");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(parse("[1, 2"), Err(BombermanError::InvalidJson));
+        assert_eq!(parse("{\"a\" 1}"), Err(BombermanError::InvalidJson));
+        assert_eq!(parse("[] x"), Err(BombermanError::InvalidJson));
+        let deep = "[".repeat(200_000);
+        assert_eq!(parse(&deep), Err(BombermanError::InvalidJson));
+        let nested = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        assert!(parse(&nested).is_ok());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod cli;
+
 pub mod config;
 
 pub mod error;
@@ -11,3 +13,43 @@ pub mod file_io;
 pub mod item;
 
 pub mod coordinate;
+
+pub mod analysis;
+
+pub mod diff;
+
+pub mod board;
+
+pub mod detonation;
+
+pub mod format;
+
+pub mod json;
+
+pub mod validation;
+
+pub mod header;
+
+pub mod suite;
+
+pub mod svg;
+
+pub mod png;
+
+pub mod terminal;
+
+pub mod generate;
+
+pub mod batch;
+
+pub mod i18n;
+
+pub mod trace;
+
+pub mod watch;
+
+pub mod settings;
+
+pub mod repl;
+
+pub mod xml;
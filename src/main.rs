@@ -1,35 +1,788 @@
-use bomberman::config::Config;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bomberman::board::Board;
+use bomberman::cli::{self, Command, Flag, Matches, Status};
+use bomberman::config::{self, Config};
+use bomberman::constants::STANDARD_STREAM;
+use bomberman::detonation::DetonationReport;
+use bomberman::error::{BombermanError, ErrorCategory};
+use bomberman::format::{self, Format, LineFormat};
+use bomberman::generate::{self, Options};
+use bomberman::i18n::{self, Lang, Message};
+use bomberman::repl::{Reply, Session};
+use bomberman::settings::{Settings, SETTINGS_FILE};
+use bomberman::suite::{self, Outcome};
+use bomberman::watch::{self, Watcher};
+use bomberman::{analysis, batch, diff, file_io, png, svg, terminal, trace, validation};
 
 use bomberman::game::Game;
 
+const HELP_COMMAND: &str = "help";
+const SHORT_HELP_FLAG: &str = "-h";
+const DIFF_EXTENSION: &str = ".diff";
+const PNG_EXTENSION: &str = ".png";
+
+const SIZE_FLAG: &str = "--size";
+const SEED_FLAG: &str = "--seed";
+const BOMBS_FLAG: &str = "--bombs";
+const ENEMIES_FLAG: &str = "--enemies";
+const ROCKS_FLAG: &str = "--rocks";
+const DEFLECTIONS_FLAG: &str = "--deflections";
+const OUTPUT_FLAG: &str = "--output";
+const FORMAT_FLAG: &str = "--format";
+const MANIFEST_FLAG: &str = "--manifest";
+const JUNIT_FLAG: &str = "--junit";
+const JOBS_FLAG: &str = "--jobs";
+const INTERVAL_FLAG: &str = "--interval";
+const ERROR_FORMAT_FLAG: &str = "--error-format";
+const LANG_FLAG: &str = "--lang";
+/// Variable de entorno con la configuracion regional, usada si no se indica `--lang`.
+const LANG_VARIABLE: &str = "LANG";
+/// Manifiesto que se busca en la carpeta del lote si no se indica `--manifest`.
+const DEFAULT_MANIFEST: &str = "cases.txt";
+/// Reporte JUnit que se escribe si no se indica `--junit`.
+const DEFAULT_JUNIT: &str = "junit.xml";
+/// Milisegundos entre cada consulta del archivo observado si no se indica `--interval`.
+const DEFAULT_INTERVAL: u64 = 500;
+
+/// Comandos del programa. El primero (`run`) es el que se usa si el primer argumento no es el
+/// nombre de ningun comando, para mantener la forma `bomberman tablero.txt path/output x y`.
+const COMMANDS: &[Command] = &[
+    Command {
+        name: "run",
+        args: "tablero.txt path/output [x y]",
+        about: Message {
+            es: "Detona una bomba y guarda el tablero final",
+            en: "Detonates a bomb and saves the final board",
+        },
+        flags: config::FLAGS,
+        run,
+    },
+    Command {
+        name: "check",
+        args: "tablero.txt",
+        about: Message {
+            es: "Informa los enemigos que no pueden ser derrotados",
+            en: "Reports the enemies that cannot be defeated",
+        },
+        flags: &[],
+        run: check,
+    },
+    Command {
+        name: "render",
+        args: "tablero.txt imagen.svg|imagen.png [x y]",
+        about: Message {
+            es: "Dibuja el tablero y la detonacion en SVG o PNG",
+            en: "Draws the board and the detonation as SVG or PNG",
+        },
+        flags: &[],
+        run: render,
+    },
+    Command {
+        name: "solve",
+        args: "tablero.txt",
+        about: Message {
+            es: "Prueba cada bomba y muestra cual derrota mas enemigos",
+            en: "Tries every bomb and shows which one defeats the most enemies",
+        },
+        flags: &[],
+        run: solve,
+    },
+    Command {
+        name: "generate",
+        args: "",
+        about: Message {
+            es: "Genera un tablero al azar",
+            en: "Generates a random board",
+        },
+        flags: GENERATE_FLAGS,
+        run: generate,
+    },
+    Command {
+        name: "validate",
+        args: "tablero.txt",
+        about: Message {
+            es: "Lista todos los errores del tablero",
+            en: "Lists every error in the board",
+        },
+        flags: &[],
+        run: validate,
+    },
+    Command {
+        name: "suite",
+        args: "casos.txt path/resultado.txt",
+        about: Message {
+            es: "Ejecuta los casos de un archivo con varios tableros",
+            en: "Runs the cases of a file with several boards",
+        },
+        flags: &[],
+        run: run_suite,
+    },
+    Command {
+        name: "batch",
+        args: "carpeta",
+        about: Message {
+            es: "Ejecuta en paralelo los tableros de un manifiesto y escribe un reporte JUnit",
+            en: "Runs the boards of a manifest in parallel and writes a JUnit report",
+        },
+        flags: BATCH_FLAGS,
+        run: run_batch,
+    },
+    Command {
+        name: "diff",
+        args: "tablero.txt path/output [x y]",
+        about: Message {
+            es: "Detona una bomba y muestra las casillas que cambiaron",
+            en: "Detonates a bomb and shows the cells that changed",
+        },
+        flags: config::FLAGS,
+        run: diff,
+    },
+    Command {
+        name: "explain",
+        args: "tablero.txt x y",
+        about: Message {
+            es: "Lista las bombas cuya cadena de explosiones alcanza una casilla",
+            en: "Lists the bombs whose chain of explosions reaches a cell",
+        },
+        flags: &[],
+        run: explain,
+    },
+    Command {
+        name: "watch",
+        args: "tablero.txt [x y]",
+        about: Message {
+            es: "Vuelve a detonar la bomba y muestra el tablero cada vez que se guarda el archivo",
+            en: "Detonates the bomb again and shows the board every time the file is saved",
+        },
+        flags: WATCH_FLAGS,
+        run: watch,
+    },
+    Command {
+        name: "repl",
+        args: "tablero.txt",
+        about: Message {
+            es: "Abre una consola para detonar, editar y deshacer cambios sobre el tablero",
+            en: "Opens a console to detonate, edit and undo changes on the board",
+        },
+        flags: &[],
+        run: repl,
+    },
+];
+
+const GENERATE_FLAGS: &[Flag] = &[
+    Flag {
+        name: SIZE_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de filas y columnas, hasta 1000 (7 por defecto)",
+            en: "Number of rows and columns, up to 1000 (7 by default)",
+        },
+    },
+    Flag {
+        name: SEED_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Semilla del generador (por defecto, una distinta en cada ejecucion)",
+            en: "Generator seed (by default, a different one on each run)",
+        },
+    },
+    Flag {
+        name: BOMBS_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de bombas (2 por defecto)",
+            en: "Number of bombs (2 by default)",
+        },
+    },
+    Flag {
+        name: ENEMIES_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de enemigos (3 por defecto)",
+            en: "Number of enemies (3 by default)",
+        },
+    },
+    Flag {
+        name: ROCKS_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de rocas (4 por defecto)",
+            en: "Number of rocks (4 by default)",
+        },
+    },
+    Flag {
+        name: DEFLECTIONS_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de desvios (1 por defecto)",
+            en: "Number of deflections (1 by default)",
+        },
+    },
+    Flag {
+        name: OUTPUT_FLAG,
+        value: Some("ruta"),
+        help: Message {
+            es: "Archivo donde se guarda el tablero, o - para la salida estandar (por defecto)",
+            en: "File where the board is saved, or - for standard output (default)",
+        },
+    },
+    Flag {
+        name: FORMAT_FLAG,
+        value: Some("formato"),
+        help: Message {
+            es: "Formato del tablero: plain o rle",
+            en: "Board format: plain or rle",
+        },
+    },
+];
+
+/// Opciones que valen para todos los comandos.
+const GLOBAL_FLAGS: &[Flag] = &[
+    Flag {
+        name: ERROR_FORMAT_FLAG,
+        value: Some("text|json"),
+        help: Message {
+            es: "Formato de los errores en la salida de errores (text por defecto)",
+            en: "Format of the errors on standard error (text by default)",
+        },
+    },
+    Flag {
+        name: LANG_FLAG,
+        value: Some("es|en"),
+        help: Message {
+            es: "Idioma de los mensajes (por defecto, el de la variable LANG o es)",
+            en: "Language of the messages (by default, the one in the LANG variable or es)",
+        },
+    },
+];
+
+const WATCH_FLAGS: &[Flag] = &[Flag {
+    name: INTERVAL_FLAG,
+    value: Some("ms"),
+    help: Message {
+        es: "Milisegundos entre cada consulta del archivo (500 por defecto)",
+        en: "Milliseconds between each check of the file (500 by default)",
+    },
+}];
+
+const BATCH_FLAGS: &[Flag] = &[
+    Flag {
+        name: MANIFEST_FLAG,
+        value: Some("ruta"),
+        help: Message {
+            es: "Manifiesto con una linea 'tablero.txt x y [esperado.txt]' por caso (carpeta/cases.txt por defecto)",
+            en: "Manifest with one line 'board.txt x y [expected.txt]' per case (directory/cases.txt by default)",
+        },
+    },
+    Flag {
+        name: JUNIT_FLAG,
+        value: Some("ruta"),
+        help: Message {
+            es: "Archivo donde se escribe el reporte JUnit (junit.xml por defecto)",
+            en: "File where the JUnit report is written (junit.xml by default)",
+        },
+    },
+    Flag {
+        name: JOBS_FLAG,
+        value: Some("n"),
+        help: Message {
+            es: "Cantidad de hilos (por defecto, uno por procesador)",
+            en: "Number of threads (by default, one per processor)",
+        },
+    },
+];
+
 fn main() {
-    let conf = match Config::new() {
-        Ok(conf) => conf,
-        Err(e) => {
-            // Los errores del config se imprimiran por stdin
-            // para mejorar la efeciencia del programa
-            println!("Error: {}", e.message());
+    let mut args: Vec<String> = env::args().collect();
+    let locale = env::var(LANG_VARIABLE).ok();
+    let lang = match cli::take_global(&mut args, LANG_FLAG) {
+        Ok(lang) => lang,
+        Err(e) => fail(&e, LineFormat::Text),
+    };
+    match Lang::resolve(lang.as_deref(), locale.as_deref()) {
+        Ok(lang) => Lang::set_current(lang),
+        Err(e) => fail(&e, LineFormat::Text),
+    }
+    let error_format = match cli::take_global(&mut args, ERROR_FORMAT_FLAG)
+        .and_then(|name| name.map_or(Ok(LineFormat::Text), |n| LineFormat::from_name(&n)))
+    {
+        Ok(format) => format,
+        Err(e) => fail(&e, LineFormat::Text),
+    };
+    let file_settings = match Settings::load(Path::new(SETTINGS_FILE)) {
+        Ok(settings) => settings,
+        Err(e) => fail(&e, error_format),
+    };
+    // `--lang` tiene prioridad sobre el archivo de configuracion, y ambos sobre `LANG`.
+    let flag_settings = Settings {
+        lang: lang.is_some().then(Lang::current),
+        ..Settings::default()
+    };
+    let settings = flag_settings.over(file_settings);
+    if let Some(lang) = settings.lang {
+        Lang::set_current(lang);
+    }
+    let (command, rest) = match args.get(1).map(String::as_str) {
+        None => {
+            eprint!("{}", cli::overview(COMMANDS, GLOBAL_FLAGS));
+            process::exit(BombermanError::InsufficientInput.category().exit_code());
+        }
+        Some(HELP_COMMAND | cli::HELP_FLAG | SHORT_HELP_FLAG) => {
+            match args.get(2).and_then(|name| cli::find(COMMANDS, name)) {
+                Some(command) => print!("{}", command.help()),
+                None => print!("{}", cli::overview(COMMANDS, GLOBAL_FLAGS)),
+            }
             return;
         }
+        Some(name) => match cli::find(COMMANDS, name) {
+            Some(command) => (command, &args[2..]),
+            None => (&COMMANDS[0], &args[1..]),
+        },
     };
-    let mut game = match Game::new(&conf) {
-        Ok(game) => game,
-        Err(e) => {
-            e.send(conf.path_output);
-            return;
+
+    let result = command.parse(rest).and_then(|matches| {
+        if matches.help {
+            print!("{}", command.help());
+            Ok(Status::Success)
+        } else {
+            (command.run)(&matches, &settings)
+        }
+    });
+    match result {
+        Ok(Status::Success) => (),
+        Ok(Status::Failure) => process::exit(1),
+        Ok(Status::Invalid(errors)) => {
+            for e in &errors {
+                eprintln!("{}", e.diagnostic(error_format));
+            }
+            process::exit(errors.first().map_or(1, |e| e.category().exit_code()));
+        }
+        Err(BombermanError::InsufficientInput) if error_format == LineFormat::Text => {
+            eprintln!("{}", i18n::USAGE.fill(&[&command.usage()]));
+            process::exit(BombermanError::InsufficientInput.category().exit_code());
+        }
+        Err(e) => fail(&e, error_format),
+    }
+}
+
+/// Informa el error en la salida de errores y termina con el codigo de salida de su categoria.
+fn fail(e: &BombermanError, format: LineFormat) -> ! {
+    eprintln!("{}", e.diagnostic(format));
+    process::exit(e.category().exit_code());
+}
+
+/// Escribe el error de una ejecucion en el archivo de salida, como la salida del programa, y lo
+/// devuelve para que tambien se informe en la salida de errores. Si la salida es `-` o el error
+/// es justamente que no se puede escribir la salida, no escribe nada.
+fn write_error_file(conf: &Config, e: BombermanError) -> BombermanError {
+    if conf.path_output != STANDARD_STREAM && e.category() != ErrorCategory::OutputIo {
+        match conf.format {
+            Format::Plain | Format::RunLength => e.send(conf.path_output.clone()),
+            Format::Json => e.send_json(conf.path_output.clone()),
         }
+    }
+    e
+}
+
+/// Ejecuta `run tablero.txt path/output [x y]`: detona la bomba indicada y guarda el tablero
+/// final. Los errores de la ejecucion se escriben en el archivo de salida.
+fn run(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let conf = Config::new(settings, matches)?;
+    conf.prepare_output()?;
+    let result = match conf.format {
+        Format::Plain | Format::RunLength => detonate(&conf),
+        Format::Json => run_json(&conf),
     };
+    result.map_err(|e| write_error_file(&conf, e))?;
+    Ok(Status::Success)
+}
 
-    match game.denotate_bomb(conf.x as u32, conf.y as u32) {
-        Ok(_) => (),
-        Err(e) => {
-            e.send(conf.path_output);
-            return;
+/// Detona la bomba indicada en la configuracion y guarda el tablero final, en el formato de
+/// texto comun o en el comprimido segun la configuracion.
+fn detonate(conf: &Config) -> Result<(), BombermanError> {
+    let game = Game::new(conf)?;
+    print_warnings(&game);
+    let (x, y) = conf.bomb(&game)?;
+    let (board, report) = game.simulate(x, y)?;
+    print_trace(conf, &report);
+    if conf.show {
+        show(conf, game.board(), &board, &report);
+    }
+    let output = match conf.format {
+        Format::RunLength => format::board_to_run_length(&board),
+        _ => board.to_string(),
+    };
+    file_io::write_output(&conf.path_output, &output)
+}
+
+/// Ejecuta la detonacion leyendo el tablero en JSON y escribe en JSON el tablero final junto con
+/// el detalle de la detonacion.
+fn run_json(conf: &Config) -> Result<(), BombermanError> {
+    let text = file_io::read_to_string(&conf.name_input)?;
+    let game = Game::from_board(format::board_from_json(&text)?).with_rules(&conf.rules);
+    let (x, y) = conf.bomb(&game)?;
+    let (board, report) = game.simulate(x, y)?;
+    print_trace(conf, &report);
+    if conf.show {
+        show(conf, game.board(), &board, &report);
+    }
+    let result = format!("{}\n", format::result_to_json(&board, &report));
+    file_io::write_output(&conf.path_output, &result)
+}
+
+/// Muestra en la terminal el tablero inicial y el final (`--show`). Si el resultado se escribe
+/// en la salida estandar, la vista va a la salida de errores. Solo se usan colores si la salida
+/// elegida es una terminal.
+fn show(conf: &Config, before: &Board, after: &Board, report: &DetonationReport) {
+    if conf.path_output == STANDARD_STREAM {
+        let colour = io::stderr().is_terminal();
+        eprint!("{}", terminal::render(before, after, report, colour));
+    } else {
+        let colour = io::stdout().is_terminal();
+        print!("{}", terminal::render(before, after, report, colour));
+    }
+}
+
+/// Muestra el registro de la propagacion de la detonacion (`--trace`), con el mismo criterio que
+/// `show` para elegir entre la salida estandar y la de errores.
+fn print_trace(conf: &Config, report: &DetonationReport) {
+    let format = match conf.trace {
+        Some(format) => format,
+        None => return,
+    };
+    let text = trace::render(&report.trace, format);
+    if conf.path_output == STANDARD_STREAM {
+        eprint!("{}", text);
+    } else {
+        print!("{}", text);
+    }
+}
+
+/// Muestra en la salida de errores las correcciones que se aplicaron al archivo de entrada.
+fn print_warnings(game: &Game) {
+    for warning in game.warnings() {
+        eprintln!("{}", i18n::WARNING.fill(&[warning]));
+    }
+}
+
+/// Ejecuta `check tablero.txt`: informa los enemigos que no pueden ser derrotados y
+/// termina con codigo distinto de cero si el tablero no se puede ganar.
+fn check(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let path = &matches.expect_positional(&[1])?[0];
+    let game = Game::from_file(path)?.with_rules(settings.rules());
+    print_warnings(&game);
+
+    let report = analysis::check(&game);
+    print!("{}", report);
+    if !report.is_winnable() {
+        return Ok(Status::Failure);
+    }
+    Ok(Status::Success)
+}
+
+/// Ejecuta `solve tablero.txt`: detona por separado cada bomba del tablero y las lista de mejor a
+/// peor segun los enemigos que derrotan. Termina con codigo distinto de cero si ninguna bomba
+/// derrota a todos los enemigos.
+fn solve(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let path = &matches.expect_positional(&[1])?[0];
+    let game = Game::from_file(path)?.with_rules(settings.rules());
+    print_warnings(&game);
+
+    let (text, winner) = analysis::describe_solutions(&game);
+    print!("{}", text);
+    if !winner {
+        return Ok(Status::Failure);
+    }
+    Ok(Status::Success)
+}
+
+/// Ejecuta `generate`: genera un tablero al azar y lo guarda con un encabezado que indica la
+/// semilla usada y la primera bomba como bomba a detonar.
+fn generate(matches: &Matches, _settings: &Settings) -> Result<Status, BombermanError> {
+    matches.expect_positional(&[0])?;
+    let defaults = Options::default();
+    let invalid = |flag: &str| BombermanError::InvalidFlagValue(flag.to_string());
+    let count = |flag: &str, default: usize| match matches.value(flag) {
+        Some(value) => value.parse::<usize>().map_err(|_| invalid(flag)),
+        None => Ok(default),
+    };
+    let size = match matches.value(SIZE_FLAG) {
+        Some(value) => value
+            .parse::<u32>()
+            .ok()
+            .filter(|size| (generate::MIN_SIZE..=generate::MAX_SIZE).contains(size))
+            .ok_or_else(|| invalid(SIZE_FLAG))?,
+        None => defaults.size,
+    };
+    let seed = match matches.value(SEED_FLAG) {
+        Some(value) => value.parse::<u64>().map_err(|_| invalid(SEED_FLAG))?,
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default(),
+    };
+    let options = Options {
+        size,
+        seed,
+        bombs: count(BOMBS_FLAG, defaults.bombs)?,
+        enemies: count(ENEMIES_FLAG, defaults.enemies)?,
+        rocks: count(ROCKS_FLAG, defaults.rocks)?,
+        deflections: count(DEFLECTIONS_FLAG, defaults.deflections)?,
+    };
+    let format = match matches.value(FORMAT_FLAG) {
+        Some(name) => Format::from_name(name)?,
+        None => Format::default(),
+    };
+
+    let board = generate::generate(&options)?;
+    let mut text = format!("# seed: {}\n", options.seed);
+    if let Some(bomb) = Game::from_board(board.clone()).bombs().first() {
+        text.push_str(&format!("# detonate: {} {}\n", bomb.y, bomb.x));
+    }
+    match format {
+        Format::RunLength => text.push_str(&format::board_to_run_length(&board)),
+        Format::Plain => text.push_str(&board.to_string()),
+        Format::Json => return Err(BombermanError::InvalidFormat),
+    }
+    file_io::write_output(matches.value(OUTPUT_FLAG).unwrap_or(STANDARD_STREAM), &text)?;
+    Ok(Status::Success)
+}
+
+/// Ejecuta `validate tablero.txt`: lista todos los errores del tablero, uno por linea, y termina
+/// con codigo distinto de cero si encontro alguno.
+fn validate(matches: &Matches, _settings: &Settings) -> Result<Status, BombermanError> {
+    let path = &matches.expect_positional(&[1])?[0];
+    let content = file_io::read_to_string(path)?;
+    let normalized = file_io::normalize(&content);
+    for warning in &normalized.warnings {
+        eprintln!("{}", i18n::WARNING.fill(&[warning]));
+    }
+
+    let errors = validation::validate(&normalized.text);
+
+    if errors.is_empty() {
+        println!("{}", i18n::VALID_BOARD.text());
+        return Ok(Status::Success);
+    }
+    Ok(Status::Invalid(errors))
+}
+
+/// Ejecuta `suite casos.txt path/resultado.txt`: ejecuta cada caso de un archivo con varios
+/// tableros, escribe el archivo de resultados combinado y termina con codigo distinto de cero si
+/// algun caso fallo o no se pudo ejecutar.
+fn run_suite(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let args = matches.expect_positional(&[2])?;
+    let content = file_io::read_to_string(&args[0])?;
+
+    let results = suite::run_suite(&content, settings.rules());
+    file_io::write_output(&args[1], &suite::report(&results))?;
+    if results
+        .iter()
+        .any(|r| matches!(r.outcome(), Outcome::Failed | Outcome::Error))
+    {
+        return Ok(Status::Failure);
+    }
+    Ok(Status::Success)
+}
+
+/// Ejecuta `batch carpeta --manifest cases.txt`: ejecuta en paralelo cada linea del manifiesto,
+/// imprime los casos que fallaron y un resumen, y escribe el reporte JUnit. Termina con codigo
+/// distinto de cero si algun caso fallo o no se pudo ejecutar.
+fn run_batch(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let dir = Path::new(&matches.expect_positional(&[1])?[0]);
+    let manifest = match matches.value(MANIFEST_FLAG) {
+        Some(path) => path.to_string(),
+        None => dir.join(DEFAULT_MANIFEST).to_string_lossy().into_owned(),
+    };
+    let workers = match matches.value(JOBS_FLAG) {
+        Some(jobs) => config::get_coordinate(jobs)?,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let entries = batch::parse_manifest(&file_io::read_to_string(&manifest)?)?;
+
+    let results = batch::run_batch(dir, entries, workers, settings.rules());
+    for result in &results {
+        if let outcome @ (Outcome::Failed | Outcome::Error) = result.outcome() {
+            let args: [&dyn std::fmt::Display; 3] = [
+                &suite::outcome_name(outcome),
+                &result.entry.name(),
+                &result.entry.line,
+            ];
+            println!("{}", i18n::BATCH_CASE.fill(&args));
+        }
+    }
+    println!("{}", suite::summary(results.iter().map(|r| r.outcome())));
+    file_io::write_output(
+        matches.value(JUNIT_FLAG).unwrap_or(DEFAULT_JUNIT),
+        &batch::junit(&results),
+    )?;
+    if results
+        .iter()
+        .any(|r| matches!(r.outcome(), Outcome::Failed | Outcome::Error))
+    {
+        return Ok(Status::Failure);
+    }
+    Ok(Status::Success)
+}
+
+/// Ejecuta `render tablero.txt imagen.svg [x y]`: dibuja el tablero en SVG. Si se indica una
+/// bomba (o el tablero tiene `# detonate:` en su encabezado), dibuja tambien sus rafagas y el
+/// orden de la cadena de explosiones.
+///
+/// Si la imagen termina en `.png`, dibuja en PNG el tablero inicial, el final y las casillas
+/// alcanzadas por la detonacion.
+fn render(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let args = matches.expect_positional(&[2, 4])?;
+    let game = Game::from_file(&args[0])?.with_rules(settings.rules());
+    print_warnings(&game);
+
+    let order = settings.coordinates.unwrap_or_default();
+    let bomb = match args.get(2..4) {
+        Some([first, second]) => Some(order.bomb(first, second)?),
+        _ => game.header().detonate,
+    };
+    let result = match bomb {
+        Some((x, y)) => {
+            let (x, y) = config::cell(x, y)?;
+            Some(game.simulate(x, y)?)
         }
+        None => None,
     };
 
-    match game.save_game(&conf.path_output) {
-        Ok(_) => (),
-        Err(e) => e.send(conf.path_output),
+    let image = if args[1].ends_with(PNG_EXTENSION) {
+        match &result {
+            Some((board, report)) => png::render(game.board(), board, &report.affected)?,
+            None => png::render(game.board(), game.board(), &Default::default())?,
+        }
+    } else {
+        svg::render(
+            game.board(),
+            result.as_ref().map(|(_, report)| report),
+            game.header().title.as_deref(),
+        )
+        .into_bytes()
+    };
+    file_io::write_bytes(&args[1], &image)?;
+    Ok(Status::Success)
+}
+
+/// Ejecuta `diff tablero.txt path/output x y`: guarda el tablero final como una ejecucion normal,
+/// imprime las casillas que cambiaron y escribe en `<output>.diff` la vista lado a lado del
+/// tablero inicial y el final.
+fn diff(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let conf = Config::new(settings, matches)?;
+    let report_path = format!("{}{}", conf.path_output, DIFF_EXTENSION);
+    conf.prepare_output()?;
+    conf.prepare_path(&report_path)?;
+    let before = Game::new(&conf).map_err(|e| write_error_file(&conf, e))?;
+    print_warnings(&before);
+    let mut after = before.clone();
+    conf.bomb(&before)
+        .and_then(|(x, y)| after.denotate_bomb(x, y))
+        .and_then(|_| after.save_game(&conf.path_output))
+        .map_err(|e| write_error_file(&conf, e))?;
+
+    let mut report = diff::side_by_side(&before, &after);
+    report.push('\n');
+    for change in diff::diff(&before, &after) {
+        println!("{}", change);
+        report.push_str(&format!("{}\n", change));
+    }
+    file_io::write_output(&report_path, &report)?;
+    Ok(Status::Success)
+}
+
+/// Ejecuta `explain tablero.txt x y`: lista las bombas cuya cadena de explosiones alcanza la
+/// casilla indicada, con el recorrido de cada rafaga.
+fn explain(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let args = matches.expect_positional(&[3])?;
+    let order = settings.coordinates.unwrap_or_default();
+    let (x, y) = order.bomb(&args[1], &args[2])?;
+    let (x, y) = config::cell(x, y)?;
+    let game = Game::from_file(&args[0])?.with_rules(settings.rules());
+    print_warnings(&game);
+
+    let target = game.cell(x, y)?;
+    let routes = analysis::bombs_reaching(&game, &target);
+    if routes.is_empty() {
+        println!("{}", i18n::EXPLAIN_NONE.fill(&[&target]));
+    }
+    for route in routes {
+        print!("{}", route.describe(game.board()));
+    }
+    Ok(Status::Success)
+}
+
+/// Ejecuta `watch tablero.txt [x y]`: consulta la fecha de modificacion del tablero cada
+/// `--interval` milisegundos y, cada vez que cambia, borra la terminal y vuelve a mostrar la
+/// detonacion. Los errores del tablero se muestran en pantalla sin dejar de observar.
+fn watch(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let args = matches.expect_positional(&[1, 3])?;
+    let order = settings.coordinates.unwrap_or_default();
+    let bomb = match args {
+        [_, first, second] => {
+            let (x, y) = order.bomb(first, second)?;
+            Some(config::cell(x, y)?)
+        }
+        _ => None,
+    };
+    let interval = match matches.value(INTERVAL_FLAG) {
+        Some(value) => config::get_coordinate(value)? as u64,
+        None => DEFAULT_INTERVAL,
+    };
+    let colour = io::stdout().is_terminal();
+    let mut watcher = Watcher::new(&args[0]);
+    loop {
+        if watcher.changed() {
+            if colour {
+                print!("{}", watch::CLEAR_SCREEN);
+            }
+            print!("{}", watch::frame(&args[0], bomb, settings.rules(), colour));
+            let _ = io::stdout().flush();
+        }
+        thread::sleep(Duration::from_millis(interval));
+    }
+}
+
+/// Ejecuta `repl tablero.txt`: lee comandos de la entrada estandar, uno por linea, y los ejecuta
+/// sobre el tablero en memoria hasta recibir `quit` o el fin de la entrada. Los errores de cada
+/// comando se informan sin salir de la consola.
+fn repl(matches: &Matches, settings: &Settings) -> Result<Status, BombermanError> {
+    let path = &matches.expect_positional(&[1])?[0];
+    let game = Game::from_file(path)?;
+    print_warnings(&game);
+
+    let interactive = io::stdin().is_terminal();
+    let mut session = Session::new(
+        game,
+        settings.coordinates.unwrap_or_default(),
+        settings.rules(),
+        io::stdout().is_terminal(),
+    );
+    if interactive {
+        println!("{}", i18n::REPL_WELCOME.fill(&[path]));
+    }
+    let mut lines = io::stdin().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+        let line = match lines.next() {
+            Some(line) => line.map_err(|_| BombermanError::InputPathError)?,
+            None => break,
+        };
+        match session.execute(&line) {
+            Ok(Reply::Output(text)) => print!("{}", text),
+            Ok(Reply::Quit) => break,
+            Err(e) => eprintln!("Error: {}", e.message()),
+        }
     }
+    Ok(Status::Success)
 }
@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+
+use crate::board::Board;
+use crate::constants::{DOWN, LEFT, RIGHT};
+use crate::coordinate::Coordinate;
+use crate::error::BombermanError;
+use crate::item::Item;
+
+/// Mayor cantidad de filas de un tablero que se puede dibujar; limita la imagen a unos 50 MB en
+/// memoria.
+pub const MAX_DIMENSION: u32 = 100;
+/// Lado de cada casilla, en pixeles.
+const TILE: usize = 24;
+/// Escala con la que se dibujan los simbolos de 8x8 del tileset.
+const GLYPH_SCALE: usize = 2;
+/// Margen alrededor de la imagen y separacion entre tableros, en pixeles.
+const GAP: usize = 8;
+
+type Rgb = [u8; 3];
+
+/// Filtros de PNG que guardan cada byte como diferencia con el del pixel de la izquierda (`Sub`)
+/// o con el de la fila de arriba (`Up`).
+const SUB_FILTER: u8 = 1;
+const UP_FILTER: u8 = 2;
+/// Simbolo deflate que indica el fin del bloque.
+const END_OF_BLOCK: u16 = 256;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// Longitud minima de cada codigo de longitud de deflate (257 a 285) y sus bits extra.
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+const BACKGROUND: Rgb = [255, 255, 255];
+const EMPTY_COLOUR: Rgb = [235, 235, 235];
+const GRID_COLOUR: Rgb = [200, 200, 200];
+const DIGIT_COLOUR: Rgb = [0, 0, 0];
+const BLAST_COLOUR: Rgb = [255, 140, 0];
+
+/// Simbolos de 8x8 del tileset; cada `u8` es una fila y el bit mas significativo es la columna
+/// de la izquierda.
+const ENEMY_GLYPH: [u8; 8] = [
+    0b00111100, 0b01111110, 0b11011011, 0b11111111, 0b11111111, 0b10111101, 0b11000011, 0b01111110,
+];
+const BOMB_GLYPH: [u8; 8] = [
+    0b00000110, 0b00001000, 0b00111100, 0b01111110, 0b01111110, 0b01111110, 0b00111100, 0b00000000,
+];
+const TRANSFER_GLYPH: [u8; 8] = [
+    0b00011000, 0b00111100, 0b01111110, 0b11111111, 0b11111111, 0b01111110, 0b00111100, 0b00011000,
+];
+const ROCK_GLYPH: [u8; 8] = [
+    0b00000000, 0b00111000, 0b01111100, 0b11111110, 0b11111111, 0b11111111, 0b01111110, 0b00000000,
+];
+const WALL_GLYPH: [u8; 8] = [
+    0b11111111, 0b00010001, 0b11111111, 0b01000100, 0b11111111, 0b00010001, 0b11111111, 0b01000100,
+];
+/// Flecha hacia arriba; las demas direcciones se obtienen rotandola.
+const ARROW_GLYPH: [u8; 8] = [
+    0b00011000, 0b00111100, 0b01111110, 0b11111111, 0b00011000, 0b00011000, 0b00011000, 0b00011000,
+];
+
+/// Digitos de 3x5 pixeles; cada `u8` es una fila y el bit 2 es la columna de la izquierda.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Imagen RGB en memoria.
+///
+/// # Campos
+///
+/// * `width`: Ancho en pixeles.
+/// * `height`: Alto en pixeles.
+/// * `pixels`: Color de cada pixel, fila por fila.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Crea una imagen de `width` x `height` pixeles pintada con `colour`.
+    pub fn new(width: usize, height: usize, colour: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![colour; width * height],
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    fn fill(&mut self, left: usize, top: usize, width: usize, height: usize, colour: Rgb) {
+        for y in top..top + height {
+            for x in left..left + width {
+                self.set(x, y, colour);
+            }
+        }
+    }
+}
+
+/// Dibuja, uno al lado del otro, el tablero inicial, el tablero final y el tablero inicial con
+/// las casillas alcanzadas por la detonacion resaltadas, y devuelve la imagen en formato PNG.
+///
+/// La imagen solo depende de los tableros y de las casillas alcanzadas, por lo que el mismo
+/// tablero siempre genera los mismos bytes.
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::BoardTooLarge` si el tablero tiene mas de `MAX_DIMENSION` filas.
+pub fn render(
+    before: &Board,
+    after: &Board,
+    affected: &HashMap<Coordinate, u32>,
+) -> Result<Vec<u8>, BombermanError> {
+    let dimension = before.dimension().max(after.dimension());
+    if dimension > MAX_DIMENSION {
+        return Err(BombermanError::BoardTooLarge);
+    }
+    let side = dimension as usize * TILE;
+    let mut image = Image::new(3 * side + 4 * GAP, side + 2 * GAP, BACKGROUND);
+    let no_blast = HashMap::new();
+    for (i, (board, blast)) in [(before, &no_blast), (after, &no_blast), (before, affected)]
+        .into_iter()
+        .enumerate()
+    {
+        draw_board(&mut image, board, blast, GAP + i * (side + GAP), GAP);
+    }
+    Ok(encode(&image))
+}
+
+fn draw_board(
+    image: &mut Image,
+    board: &Board,
+    blast: &HashMap<Coordinate, u32>,
+    left: usize,
+    top: usize,
+) {
+    for x in 0..board.dimension() {
+        for y in 0..board.dimension() {
+            let tile_left = left + y as usize * TILE;
+            let tile_top = top + x as usize * TILE;
+            let background = if blast.contains_key(&board.coordinate(x, y)) {
+                blend(EMPTY_COLOUR, BLAST_COLOUR)
+            } else {
+                EMPTY_COLOUR
+            };
+            image.fill(tile_left, tile_top, TILE, TILE, GRID_COLOUR);
+            image.fill(tile_left + 1, tile_top + 1, TILE - 1, TILE - 1, background);
+            draw_tile(image, board.get(x, y), tile_left, tile_top);
+        }
+    }
+}
+
+fn blend(a: Rgb, b: Rgb) -> Rgb {
+    [
+        ((a[0] as u16 + b[0] as u16) / 2) as u8,
+        ((a[1] as u16 + b[1] as u16) / 2) as u8,
+        ((a[2] as u16 + b[2] as u16) / 2) as u8,
+    ]
+}
+
+/// Dibuja el simbolo del item centrado en la parte de arriba de la casilla y, debajo, su vida o
+/// su alcance.
+fn draw_tile(image: &mut Image, item: Item, left: usize, top: usize) {
+    let (glyph, colour, number) = match item {
+        Item::Enemy(life) => (ENEMY_GLYPH, [214, 39, 40], Some(life)),
+        Item::NormalBomb(range) => (BOMB_GLYPH, [34, 34, 34], Some(range)),
+        Item::TransferBomb(range) => (TRANSFER_GLYPH, [123, 63, 191], Some(range)),
+        Item::Rock => (ROCK_GLYPH, [140, 140, 140], None),
+        Item::Wall => (WALL_GLYPH, [90, 62, 43], None),
+        Item::Deflection(direction) => (rotate(ARROW_GLYPH, direction), [230, 184, 0], None),
+        Item::Empty => return,
+    };
+    let glyph_left = left + (TILE - 8 * GLYPH_SCALE) / 2;
+    for (row, bits) in glyph.iter().enumerate() {
+        for column in 0..8 {
+            if bits & (0x80 >> column) != 0 {
+                image.fill(
+                    glyph_left + column * GLYPH_SCALE,
+                    top + 1 + row * GLYPH_SCALE,
+                    GLYPH_SCALE,
+                    GLYPH_SCALE,
+                    colour,
+                );
+            }
+        }
+    }
+    if let Some(number) = number {
+        draw_number(image, number, left + TILE / 2, top + 18);
+    }
+}
+
+/// Rota la flecha hacia arriba para que apunte hacia `direction`.
+fn rotate(glyph: [u8; 8], direction: char) -> [u8; 8] {
+    let bit = |row: usize, column: usize| glyph[row] & (0x80 >> column) != 0;
+    let mut rotated = [0u8; 8];
+    for (row, bits) in rotated.iter_mut().enumerate() {
+        for column in 0..8 {
+            let set = match direction {
+                RIGHT => bit(7 - column, row),
+                LEFT => bit(column, 7 - row),
+                DOWN => bit(7 - row, column),
+                _ => bit(row, column),
+            };
+            if set {
+                *bits |= 0x80 >> column;
+            }
+        }
+    }
+    rotated
+}
+
+/// Escribe `number` con los digitos de 3x5 centrado horizontalmente en `center`.
+fn draw_number(image: &mut Image, number: u32, center: usize, top: usize) {
+    let digits: Vec<usize> = number
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as usize)
+        .collect();
+    let width = digits.len() * 4 - 1;
+    let left = center.saturating_sub(width / 2);
+    for (i, digit) in digits.iter().enumerate() {
+        for (row, bits) in DIGITS[*digit].iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) != 0 {
+                    image.set(left + i * 4 + column, top + row, DIGIT_COLOUR);
+                }
+            }
+        }
+    }
+}
+
+/// Codifica la imagen en formato PNG (RGB de 8 bits, sin entrelazado).
+///
+/// Las filas se filtran con `Up` o `Sub` y se comprimen con un compresor propio (ver
+/// `zlib_compress`), lo que evita depender de una biblioteca y hace que la salida sea siempre la
+/// misma.
+pub fn encode(image: &Image) -> Vec<u8> {
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&(image.width as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_chunk(&mut png, b"IHDR", &header);
+
+    let mut raw = Vec::with_capacity(image.height * (image.width * 3 + 1));
+    let mut above: &[Rgb] = &[];
+    for row in image.pixels.chunks(image.width.max(1)) {
+        raw.extend(filter_row(row, above));
+        above = row;
+    }
+    write_chunk(&mut png, b"IDAT", &zlib_compress(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Filtra una fila de pixeles para comprimirla mejor: si es igual a la fila de arriba usa el
+/// filtro `Up`, que la deja en ceros; si no, el filtro `Sub`, que deja en ceros los pixeles
+/// iguales al de su izquierda. Devuelve la fila precedida por el tipo de filtro.
+fn filter_row(row: &[Rgb], above: &[Rgb]) -> Vec<u8> {
+    if row == above {
+        let mut filtered = vec![UP_FILTER];
+        filtered.resize(row.len() * 3 + 1, 0);
+        return filtered;
+    }
+    let mut filtered = vec![SUB_FILTER];
+    let mut left = [0, 0, 0];
+    for pixel in row {
+        filtered.extend(pixel.iter().zip(left).map(|(p, l)| p.wrapping_sub(l)));
+        left = *pixel;
+    }
+    filtered
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Comprime los datos en un flujo zlib con un unico bloque deflate de codigos Huffman fijos.
+///
+/// Solo se buscan repeticiones del byte anterior (distancia 1): con el filtro `Sub` las zonas de
+/// un mismo color quedan como secuencias de ceros, que es casi todo lo que tiene una imagen de
+/// tableros. El resultado es determinista.
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    bits.write(0b011, 3);
+    let mut i = 0;
+    while i < data.len() {
+        bits.literal(data[i] as u16);
+        i += 1;
+        let mut run = 0;
+        while i + run < data.len() && data[i + run] == data[i - 1] && run < MAX_MATCH {
+            run += 1;
+        }
+        if run >= MIN_MATCH {
+            bits.length(run);
+            bits.write(0, 5);
+            i += run;
+        }
+    }
+    bits.literal(END_OF_BLOCK);
+
+    let mut out = vec![0x78, 0x01];
+    out.extend(bits.finish());
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Escritor de bits en el orden de deflate: cada byte se completa desde el bit menos significativo.
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    /// Escribe los `count` bits menos significativos de `value`, empezando por el menos significativo.
+    fn write(&mut self, value: u32, count: u32) {
+        self.buffer |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.out.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Escribe un codigo Huffman, que se guarda empezando por el bit mas significativo.
+    fn code(&mut self, code: u32, len: u32) {
+        self.write(code.reverse_bits() >> (32 - len), len);
+    }
+
+    /// Escribe un simbolo del alfabeto de literales y longitudes con los codigos fijos.
+    fn literal(&mut self, symbol: u16) {
+        let symbol = symbol as u32;
+        match symbol {
+            0..=143 => self.code(0x30 + symbol, 8),
+            144..=255 => self.code(0x190 + symbol - 144, 9),
+            256..=279 => self.code(symbol - 256, 7),
+            _ => self.code(0xc0 + symbol - 280, 8),
+        }
+    }
+
+    /// Escribe la longitud de una repeticion, entre `MIN_MATCH` y `MAX_MATCH`.
+    fn length(&mut self, length: usize) {
+        let index = LENGTH_BASES
+            .iter()
+            .rposition(|base| *base as usize <= length)
+            .unwrap_or(0);
+        self.literal(257 + index as u16);
+        self.write(
+            (length - LENGTH_BASES[index] as usize) as u32,
+            LENGTH_EXTRA_BITS[index],
+        );
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.out.push(self.buffer as u8);
+        }
+        self.out
+    }
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_encode_structure() {
+        let png = encode(&Image::new(2, 1, [255, 0, 0]));
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(
+            &png[png.len() - 8..],
+            &[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+
+    #[test]
+    fn test_compress_runs() {
+        let data = [0; 1000];
+        let compressed = zlib_compress(&data);
+        assert!(compressed.len() < 20);
+        assert_eq!(
+            &compressed[compressed.len() - 4..],
+            &adler32(&data).to_be_bytes()
+        );
+        assert!(zlib_compress(&[]).len() < 10);
+    }
+
+    #[test]
+    fn test_render_rejects_large_boards() {
+        let board = Board::new(HashMap::new(), MAX_DIMENSION + 1);
+        assert_eq!(
+            render(&board, &board, &HashMap::new()),
+            Err(BombermanError::BoardTooLarge)
+        );
+    }
+
+    #[test]
+    fn test_rotate_arrow() {
+        let right = rotate(ARROW_GLYPH, RIGHT);
+        assert_ne!(right[3] & 0b00000001, 0);
+        assert_eq!(right[0] & 0b00000001, 0);
+        assert_eq!(rotate(rotate(ARROW_GLYPH, DOWN), DOWN), ARROW_GLYPH);
+    }
+}
@@ -0,0 +1,220 @@
+use std::mem;
+
+use crate::analysis;
+use crate::config;
+use crate::constants::EMPTY_SQUARE;
+use crate::error::BombermanError;
+use crate::game::Game;
+use crate::header::Rule;
+use crate::i18n::{self, Lang};
+use crate::item::Item;
+use crate::settings::CoordinateOrder;
+use crate::terminal;
+
+/// Respuesta de la consola interactiva a una linea.
+///
+/// # Variantes
+///
+/// - `Output`: Texto a mostrar (puede estar vacio).
+/// - `Quit`: Se pidio salir de la consola.
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+/// Estado de la consola interactiva (`repl`): un unico juego en memoria sobre el que se ejecutan
+/// los comandos, junto con los estados anteriores para poder deshacer los cambios.
+///
+/// # Campos
+///
+/// * `game`: Juego actual.
+/// * `history`: Juegos anteriores a cada cambio (`detonate`, `set` y `load`), del mas viejo al mas nuevo.
+/// * `order`: Orden en que se indican las coordenadas de los comandos.
+/// * `rules`: Reglas que se agregan a las del encabezado de cada tablero cargado.
+/// * `colour`: Si es `true` las detonaciones se muestran con colores ANSI.
+#[derive(Debug)]
+pub struct Session {
+    game: Game,
+    history: Vec<Game>,
+    order: CoordinateOrder,
+    rules: Vec<Rule>,
+    colour: bool,
+}
+
+impl Session {
+    /// Crea una consola sobre `game`, al que se le agregan las reglas `rules`.
+    pub fn new(game: Game, order: CoordinateOrder, rules: &[Rule], colour: bool) -> Session {
+        Session {
+            game: game.with_rules(rules),
+            history: Vec::new(),
+            order,
+            rules: rules.to_vec(),
+            colour,
+        }
+    }
+
+    /// Devuelve el juego actual.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Ejecuta una linea de la consola. Las lineas vacias no hacen nada.
+    ///
+    /// # Argumentos
+    ///
+    /// * `line`: Comando con sus argumentos separados por espacios, por ejemplo `detonate 0 0`.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidReplCommand` si el comando no existe o no tiene la
+    /// cantidad de argumentos correcta, `BombermanError::NothingToUndo` si se pide `undo` sin
+    /// cambios previos, y los errores del comando ejecutado. Si hay un error el juego no cambia.
+    pub fn execute(&mut self, line: &str) -> Result<Reply, BombermanError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let output = match words[..] {
+            [] => String::new(),
+            ["show"] => self.game.board().to_string(),
+            ["detonate", first, second] => {
+                let (x, y) = self.order.bomb(first, second)?;
+                let (x, y) = config::cell(x, y)?;
+                let before = self.game.clone();
+                let report = self.game.denotate_bomb(x, y)?;
+                let view =
+                    terminal::render(before.board(), self.game.board(), &report, self.colour);
+                self.history.push(before);
+                view
+            }
+            ["undo"] => {
+                self.game = self.history.pop().ok_or(BombermanError::NothingToUndo)?;
+                self.game.board().to_string()
+            }
+            ["set", first, second, token] => {
+                let (x, y) = self.order.bomb(first, second)?;
+                let (x, y) = config::cell(x, y)?;
+                let item = match token {
+                    EMPTY_SQUARE => Item::Empty,
+                    token => Item::new(token)?,
+                };
+                let before = self.game.clone();
+                self.game.set(x, y, item)?;
+                self.history.push(before);
+                self.game.board().to_string()
+            }
+            ["rank"] => analysis::describe_solutions(&self.game).0,
+            ["save", path] => {
+                self.game.save_game(path)?;
+                format!("{}\n", i18n::REPL_SAVED.fill(&[&path]))
+            }
+            ["load", path] => {
+                let game = Game::from_file(path)?.with_rules(&self.rules);
+                let mut text = String::new();
+                for warning in game.warnings() {
+                    text.push_str(&i18n::WARNING.fill(&[warning]));
+                    text.push('\n');
+                }
+                self.history.push(mem::replace(&mut self.game, game));
+                text.push_str(&self.game.board().to_string());
+                text
+            }
+            ["help"] => help(self.order, Lang::current()),
+            ["quit"] | ["exit"] => return Ok(Reply::Quit),
+            _ => return Err(BombermanError::InvalidReplCommand),
+        };
+        Ok(Reply::Output(output))
+    }
+}
+
+/// Arma la ayuda de la consola en el idioma `lang`, describiendo las coordenadas de `detonate` y
+/// `set` en el orden `order`.
+fn help(order: CoordinateOrder, lang: Lang) -> String {
+    let coordinates = match order {
+        CoordinateOrder::ColumnRow => i18n::REPL_COLUMN_ROW.text_in(lang),
+        CoordinateOrder::RowColumn => i18n::REPL_ROW_COLUMN.text_in(lang),
+    };
+    format!(
+        "{}\n",
+        i18n::REPL_HELP.fill_in(lang, &[&coordinates, &coordinates])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        Session::new(game, CoordinateOrder::ColumnRow, &[], false)
+    }
+
+    #[test]
+    fn test_detonate_and_undo() {
+        let mut session = session();
+        let original = session.game().board().to_string();
+
+        assert!(matches!(
+            session.execute("detonate 0 0"),
+            Ok(Reply::Output(_))
+        ));
+        assert_ne!(session.game().board().to_string(), original);
+        assert_eq!(session.execute("undo"), Ok(Reply::Output(original.clone())));
+        assert_eq!(session.execute("undo"), Err(BombermanError::NothingToUndo));
+        assert_eq!(
+            session.execute("detonate 1 0"),
+            Err(BombermanError::InvalidBombCoordinate)
+        );
+        assert_eq!(session.game().board().to_string(), original);
+    }
+
+    #[test]
+    fn test_set_rank_and_errors() {
+        let mut session = session();
+        session.execute("set 4 0 F2").unwrap();
+        assert_eq!(session.game().get(0, 4), Item::Enemy(2));
+        assert_eq!(
+            session.execute("set 9 9 F2"),
+            Err(BombermanError::InvalidCoordinate)
+        );
+        assert_eq!(
+            session.execute("set 4294967296 0 R"),
+            Err(BombermanError::InvalidCoordinate)
+        );
+        assert!(session.execute("set 0 0 Q").is_err());
+        session.execute("set 0 0 _").unwrap();
+        assert_eq!(session.game().get(0, 0), Item::Empty);
+        assert!(matches!(session.execute("rank"), Ok(Reply::Output(text)) if !text.is_empty()));
+
+        assert_eq!(session.execute("  "), Ok(Reply::Output(String::new())));
+        assert_eq!(
+            session.execute("detonate 0"),
+            Err(BombermanError::InvalidReplCommand)
+        );
+        assert_eq!(session.execute("quit"), Ok(Reply::Quit));
+    }
+
+    #[test]
+    fn test_load_keeps_history() {
+        let mut session = session();
+        let original = session.game().board().to_string();
+        session
+            .execute("load ./tests/inputs/deviation_down.txt")
+            .unwrap();
+        assert_ne!(session.game().board().to_string(), original);
+        assert!(session
+            .execute("load ./tests/inputs/no_existe.txt")
+            .is_err());
+        session.execute("undo").unwrap();
+        assert_eq!(session.game().board().to_string(), original);
+    }
+
+    #[test]
+    fn test_help_follows_coordinate_order() {
+        let column_row = help(CoordinateOrder::ColumnRow, Lang::Es);
+        assert!(column_row.contains("detona la bomba en la columna x y la fila y"));
+        assert!(column_row.contains("reemplaza el item en la columna x y la fila y"));
+
+        let row_column = help(CoordinateOrder::RowColumn, Lang::En);
+        assert!(row_column.contains("detonates the bomb at row x and column y"));
+        assert!(row_column.contains("replaces the item at row x and column y"));
+    }
+}
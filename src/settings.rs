@@ -0,0 +1,267 @@
+use std::path::Path;
+
+use crate::config;
+use crate::error::BombermanError;
+use crate::file_io;
+use crate::format::Format;
+use crate::header::Rule;
+use crate::i18n::Lang;
+
+/// Nombre del archivo de configuracion que se busca en la carpeta de trabajo.
+pub const SETTINGS_FILE: &str = "bomberman.toml";
+
+const OUTPUT_KEY: &str = "output";
+const RULES_KEY: &str = "rules";
+const FORMAT_KEY: &str = "format";
+const COORDINATES_KEY: &str = "coordinates";
+const LANG_KEY: &str = "lang";
+
+/// Orden en que se indican las coordenadas como argumentos posicionales.
+///
+/// # Variantes
+///
+/// - `ColumnRow`: Primero la columna y despues la fila (`x y`), como en el enunciado.
+/// - `RowColumn`: Primero la fila y despues la columna.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CoordinateOrder {
+    #[default]
+    ColumnRow,
+    RowColumn,
+}
+
+impl CoordinateOrder {
+    /// Obtiene el orden a partir de su nombre (`column-row` o `row-column`).
+    pub fn from_name(name: &str) -> Option<CoordinateOrder> {
+        match name {
+            "column-row" => Some(CoordinateOrder::ColumnRow),
+            "row-column" => Some(CoordinateOrder::RowColumn),
+            _ => None,
+        }
+    }
+
+    /// Lee dos coordenadas en este orden y devuelve la fila y la columna.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidCoordinate` si alguna no es un entero no negativo.
+    pub fn bomb(self, first: &str, second: &str) -> Result<(usize, usize), BombermanError> {
+        let first = config::get_coordinate(first)?;
+        let second = config::get_coordinate(second)?;
+        match self {
+            CoordinateOrder::ColumnRow => Ok((second, first)),
+            CoordinateOrder::RowColumn => Ok((first, second)),
+        }
+    }
+}
+
+/// Capa de configuracion: valores por defecto de las opciones, cada uno presente o no.
+///
+/// Las capas se combinan con `over`, de modo que el archivo `bomberman.toml` completa lo que no
+/// se indico en la linea de comandos y los valores por defecto del programa completan el resto.
+///
+/// El archivo tiene una opcion por linea con la forma `clave = "valor"`; las listas se escriben
+/// entre corchetes (`rules = ["destroy-rocks"]`) y las lineas vacias o que empiezan con `#` se
+/// ignoran.
+///
+/// # Campos
+///
+/// * `output`: Carpeta de salida de `run` y `diff` cuando no se indica (`output = "salida"`).
+/// * `rules`: Reglas que se agregan a las del encabezado de cada tablero (`rules = ["destroy-rocks"]`).
+/// * `format`: Formato de entrada y salida si no se indica `--format` (`format = "rle"`).
+/// * `coordinates`: Orden de las coordenadas posicionales (`coordinates = "row-column"`).
+/// * `lang`: Idioma de los mensajes si no se indica `--lang` (`lang = "en"`). Tiene prioridad
+///   sobre la variable `LANG`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Settings {
+    pub output: Option<String>,
+    pub rules: Option<Vec<Rule>>,
+    pub format: Option<Format>,
+    pub coordinates: Option<CoordinateOrder>,
+    pub lang: Option<Lang>,
+}
+
+impl Settings {
+    /// Lee el texto de un archivo de configuracion.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve `BombermanError::InvalidSettings` con la linea correspondiente si alguna linea no
+    /// tiene la forma `clave = valor`, si la clave es desconocida o si el valor no es valido.
+    pub fn parse(content: &str) -> Result<Settings, BombermanError> {
+        let mut settings = Settings::default();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || file_io::is_comment(line) {
+                continue;
+            }
+            let invalid = || BombermanError::InvalidSettings { line: i + 1 };
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let values = parse_value(value).ok_or_else(invalid)?;
+            let single = match values.as_slice() {
+                [value] => Some(value.as_str()),
+                _ => None,
+            };
+            match (key.trim(), single) {
+                (OUTPUT_KEY, Some(value)) => settings.output = Some(value.to_string()),
+                (RULES_KEY, _) => {
+                    let rules = values.iter().map(|name| Rule::from_name(name));
+                    settings.rules = Some(rules.collect::<Result<_, _>>().map_err(|_| invalid())?);
+                }
+                (FORMAT_KEY, Some(value)) => {
+                    settings.format = Some(Format::from_name(value).map_err(|_| invalid())?);
+                }
+                (COORDINATES_KEY, Some(value)) => {
+                    settings.coordinates =
+                        Some(CoordinateOrder::from_name(value).ok_or_else(invalid)?);
+                }
+                (LANG_KEY, Some(value)) => {
+                    settings.lang = Some(Lang::from_name(value).map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Lee el archivo de configuracion en `path`. Si el archivo no existe devuelve una
+    /// configuracion vacia.
+    ///
+    /// # Errores
+    ///
+    /// Devuelve los errores de la lectura del archivo y los de `parse`.
+    pub fn load(path: &Path) -> Result<Settings, BombermanError> {
+        if !path.exists() {
+            return Ok(Settings::default());
+        }
+        Settings::parse(&file_io::read_to_string(&path.to_string_lossy())?)
+    }
+
+    /// Devuelve las reglas que se agregan a las de cada tablero, o ninguna si no se indicaron.
+    pub fn rules(&self) -> &[Rule] {
+        self.rules.as_deref().unwrap_or_default()
+    }
+
+    /// Combina dos capas: los valores de `self` tienen prioridad y `base` completa los que faltan.
+    pub fn over(self, base: Settings) -> Settings {
+        Settings {
+            output: self.output.or(base.output),
+            rules: self.rules.or(base.rules),
+            format: self.format.or(base.format),
+            coordinates: self.coordinates.or(base.coordinates),
+            lang: self.lang.or(base.lang),
+        }
+    }
+}
+
+/// Lee el valor de una opcion: un texto entre comillas, una palabra sin comillas o una lista de
+/// textos entre corchetes. Despues del valor puede haber un comentario que empiece con `#`.
+/// Devuelve `None` si el valor no tiene ninguna de esas formas.
+fn parse_value(text: &str) -> Option<Vec<String>> {
+    let text = text.trim();
+    let (values, rest) = match text.strip_prefix('[') {
+        Some(list) => {
+            let (items, rest) = list.split_once(']')?;
+            let values = items
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| parse_scalar(item).filter(|(_, rest)| rest.is_empty()))
+                .map(|item| item.map(|(value, _)| value))
+                .collect::<Option<Vec<String>>>()?;
+            (values, rest)
+        }
+        None => {
+            let (value, rest) = parse_scalar(text)?;
+            (vec![value], rest)
+        }
+    };
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Some(values)
+    } else {
+        None
+    }
+}
+
+/// Lee un texto entre comillas o una palabra sin comillas al principio de `text` y devuelve el
+/// valor junto con el resto del texto.
+fn parse_scalar(text: &str) -> Option<(String, &str)> {
+    match text.strip_prefix('"') {
+        Some(quoted) => {
+            let (value, rest) = quoted.split_once('"')?;
+            Some((value.to_string(), rest))
+        }
+        None => {
+            let end = text
+                .find(|c: char| c.is_whitespace() || c == '#')
+                .unwrap_or(text.len());
+            match end {
+                0 => None,
+                _ => Some((text[..end].to_string(), &text[end..])),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_settings() {
+        let content = "# proyecto\n\
+                       output = \"salida\"\n\
+                       rules = [\"destroy-rocks\"] # todas\n\
+                       format = rle\n\
+                       coordinates = \"row-column\"\n\
+                       lang = \"en\"\n";
+        let settings = Settings::parse(content).unwrap();
+        assert_eq!(settings.output.as_deref(), Some("salida"));
+        assert_eq!(settings.rules, Some(vec![Rule::DestroyRocks]));
+        assert_eq!(settings.format, Some(Format::RunLength));
+        assert_eq!(settings.coordinates, Some(CoordinateOrder::RowColumn));
+        assert_eq!(settings.lang, Some(Lang::En));
+        assert_eq!(Settings::parse("rules = []\n").unwrap().rules, Some(vec![]));
+    }
+
+    #[test]
+    fn test_parse_settings_errors() {
+        for content in [
+            "output\n",
+            "colour = \"red\"\n",
+            "format = \"xml\"\n",
+            "output = \"a\" \"b\"\n",
+            "output = \"salida\n",
+            "rules = [\"explode-walls\"]\n",
+        ] {
+            assert_eq!(
+                Settings::parse(&format!("# ok\n{}", content)),
+                Err(BombermanError::InvalidSettings { line: 2 }),
+                "{}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn test_layers_and_coordinate_order() {
+        let cli = Settings {
+            format: Some(Format::Json),
+            ..Settings::default()
+        };
+        let file = Settings {
+            output: Some("salida".to_string()),
+            format: Some(Format::RunLength),
+            ..Settings::default()
+        };
+        let settings = cli.over(file);
+        assert_eq!(settings.format, Some(Format::Json));
+        assert_eq!(settings.output.as_deref(), Some("salida"));
+
+        assert_eq!(CoordinateOrder::ColumnRow.bomb("2", "4"), Ok((4, 2)));
+        assert_eq!(CoordinateOrder::RowColumn.bomb("2", "4"), Ok((2, 4)));
+        assert_eq!(
+            Settings::load(Path::new("./tests/inputs/no_existe.toml")),
+            Ok(Settings::default())
+        );
+    }
+}
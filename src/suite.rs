@@ -0,0 +1,276 @@
+use crate::config;
+use crate::error::BombermanError;
+use crate::game::Game;
+use crate::header::{Header, Rule};
+use crate::i18n;
+
+/// Linea que separa un caso del siguiente.
+const CASE_SEPARATOR: &str = "---";
+/// Linea que separa el tablero de un caso de su salida esperada.
+const EXPECTED_SEPARATOR: &str = "===";
+
+/// Caso de un archivo con varios tableros.
+///
+/// Los casos se separan con una linea `---`. Cada caso tiene su propio encabezado (la bomba a
+/// detonar se indica con `# detonate: x y`), su tablero y, opcionalmente, luego de una linea
+/// `===`, la salida esperada: el tablero final o el mensaje de error (`Error: ...`).
+///
+/// # Campos
+///
+/// * `line`: Linea del archivo donde empieza el caso, empezando en 1.
+/// * `board`: Texto del caso, con su encabezado y su tablero.
+/// * `expected`: Salida esperada, si el caso la tiene.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub line: usize,
+    pub board: String,
+    pub expected: Option<String>,
+}
+
+/// Resultado de comparar la salida de un caso con la esperada.
+///
+/// # Variantes
+///
+/// - `Passed`: La salida coincide con la esperada.
+/// - `Failed`: La salida no coincide con la esperada.
+/// - `Error`: El caso no tiene salida esperada y fallo su ejecucion.
+/// - `Unchecked`: El caso no tiene salida esperada y se ejecuto correctamente.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Error,
+    Unchecked,
+}
+
+/// Resultado de ejecutar un caso.
+///
+/// # Campos
+///
+/// * `case`: El caso ejecutado.
+/// * `title`: Titulo del caso (`# title: ...`), si lo tiene.
+/// * `output`: El tablero final o el error de la ejecucion.
+#[derive(Debug, PartialEq)]
+pub struct CaseResult {
+    pub case: Case,
+    pub title: Option<String>,
+    pub output: Result<String, BombermanError>,
+}
+
+impl CaseResult {
+    /// Devuelve la salida del caso tal como la escribiria una ejecucion normal: el tablero final
+    /// o `Error: ...`.
+    pub fn rendered_output(&self) -> String {
+        match &self.output {
+            Ok(board) => board.clone(),
+            Err(e) => format!("Error: {}\n", e.message()),
+        }
+    }
+
+    /// Compara la salida con la esperada, ignorando los espacios al final de cada linea y las
+    /// lineas vacias al final.
+    pub fn outcome(&self) -> Outcome {
+        match (&self.case.expected, &self.output) {
+            (Some(expected), _) if same_output(expected, &self.rendered_output()) => {
+                Outcome::Passed
+            }
+            (Some(_), _) => Outcome::Failed,
+            (None, Err(_)) => Outcome::Error,
+            (None, Ok(_)) => Outcome::Unchecked,
+        }
+    }
+}
+
+/// Separa el texto de un archivo con varios tableros en sus casos. Los bloques vacios se ignoran.
+pub fn split_cases(content: &str) -> Vec<Case> {
+    let mut cases = Vec::new();
+    let mut current = Case {
+        line: 1,
+        board: String::new(),
+        expected: None,
+    };
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        match line.trim() {
+            CASE_SEPARATOR => {
+                let next = Case {
+                    line: i + 2,
+                    board: String::new(),
+                    expected: None,
+                };
+                push_case(&mut cases, std::mem::replace(&mut current, next));
+            }
+            EXPECTED_SEPARATOR => current.expected = Some(String::new()),
+            _ => match &mut current.expected {
+                Some(expected) => expected.push_str(line),
+                None => current.board.push_str(line),
+            },
+        }
+    }
+    push_case(&mut cases, current);
+    cases
+}
+
+fn push_case(cases: &mut Vec<Case>, case: Case) {
+    if !case.board.trim().is_empty() || case.expected.is_some() {
+        cases.push(case);
+    }
+}
+
+/// Ejecuta un caso: lee su tablero, le agrega las reglas `rules` y detona la bomba indicada en su
+/// encabezado.
+///
+/// Las lineas de los errores de parseo son relativas al comienzo del caso.
+pub fn run_case(case: Case, rules: &[Rule]) -> CaseResult {
+    let title = Header::parse(&case.board)
+        .ok()
+        .and_then(|header| header.title);
+    let output = Game::from_text(&case.board).and_then(|game| {
+        let game = game.with_rules(rules);
+        let (x, y) = game
+            .header()
+            .detonate
+            .ok_or(BombermanError::MissingDetonation)?;
+        let (x, y) = config::cell(x, y)?;
+        let (board, _) = game.simulate(x, y)?;
+        Ok(board.to_string())
+    });
+    CaseResult {
+        case,
+        title,
+        output,
+    }
+}
+
+/// Ejecuta en orden todos los casos del texto de un archivo con varios tableros, agregando a cada
+/// uno las reglas `rules`.
+pub fn run_suite(content: &str, rules: &[Rule]) -> Vec<CaseResult> {
+    split_cases(content)
+        .into_iter()
+        .map(|case| run_case(case, rules))
+        .collect()
+}
+
+/// Arma el archivo de resultados combinado: para cada caso, su numero, linea, titulo y
+/// resultado como comentarios, seguidos de su salida (y de la esperada si no coincide), separados
+/// con `---`. Al final agrega un resumen con la cantidad de casos de cada resultado.
+pub fn report(results: &[CaseResult]) -> String {
+    let mut text = String::new();
+    for (i, result) in results.iter().enumerate() {
+        let outcome = result.outcome();
+
+        text.push_str(&i18n::SUITE_CASE.fill(&[&(i + 1), &result.case.line]));
+        if let Some(title) = &result.title {
+            text.push_str(&format!(": {}", title));
+        }
+        text.push('\n');
+        text.push_str(&i18n::SUITE_RESULT.fill(&[&outcome_name(outcome)]));
+        text.push('\n');
+        text.push_str(&result.rendered_output());
+        if let (Outcome::Failed, Some(expected)) = (outcome, &result.case.expected) {
+            text.push_str(EXPECTED_SEPARATOR);
+            text.push('\n');
+            text.push_str(&normalize(expected).join("\n"));
+            text.push('\n');
+        }
+        text.push_str(CASE_SEPARATOR);
+        text.push('\n');
+    }
+    text.push_str(&format!(
+        "# {}\n",
+        summary(results.iter().map(CaseResult::outcome))
+    ));
+    text
+}
+
+/// Resume la cantidad de casos de cada resultado:
+/// `N caso(s): A ok, B fallo, C error, D sin salida esperada`.
+pub fn summary<I: Iterator<Item = Outcome>>(outcomes: I) -> String {
+    let mut counts = [0; 4];
+    for outcome in outcomes {
+        counts[outcome as usize] += 1;
+    }
+    i18n::SUITE_SUMMARY.fill(&[
+        &counts.iter().sum::<usize>(),
+        &counts[Outcome::Passed as usize],
+        &counts[Outcome::Failed as usize],
+        &counts[Outcome::Error as usize],
+        &counts[Outcome::Unchecked as usize],
+    ])
+}
+
+/// Compara una salida con la esperada, ignorando los espacios al final de cada linea y las
+/// lineas vacias al final.
+pub fn same_output(expected: &str, output: &str) -> bool {
+    normalize(expected) == normalize(output)
+}
+
+/// Nombre del resultado tal como aparece en los reportes.
+pub fn outcome_name(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Passed => i18n::OUTCOME_PASSED.text(),
+        Outcome::Failed => i18n::OUTCOME_FAILED.text(),
+        Outcome::Error => i18n::OUTCOME_ERROR.text(),
+        Outcome::Unchecked => i18n::OUTCOME_UNCHECKED.text(),
+    }
+}
+
+fn normalize(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_cases() {
+        let cases = split_cases("# detonate: 0 0\nB1 _\n_ _\n===\n_ _\n_ _\n---\n\n---\nW\n");
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].board, "# detonate: 0 0\nB1 _\n_ _\n");
+        assert_eq!(cases[0].expected.as_deref(), Some("_ _\n_ _\n"));
+        assert_eq!(cases[1].line, 10);
+        assert_eq!(cases[1].expected, None);
+    }
+
+    #[test]
+    fn test_run_suite_outcomes() {
+        let content = "# detonate: 0 0\nB1 _\n_ _\n===\n_ _\n_ _\n---\n\
+                       # detonate: 0 0\nB1 F1\n_ _\n===\nB1 F1\n_ _\n---\n\
+                       B1 _\n_ _\n---\n\
+                       # detonate: 0 0\nB1 _\n_ _\n";
+        let outcomes: Vec<Outcome> = run_suite(content, &[])
+            .iter()
+            .map(|r| r.outcome())
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Passed,
+                Outcome::Failed,
+                Outcome::Error,
+                Outcome::Unchecked
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expected_error() {
+        let results = run_suite(
+            "# detonate: 0 0\nB1 Z\n_ _\n===\nError: Item no reconocido (linea 2, columna 4, item 'Z')\n",
+            &[],
+        );
+        assert_eq!(results[0].outcome(), Outcome::Passed);
+    }
+
+    #[test]
+    fn test_run_suite_with_rules() {
+        let content = "# detonate: 0 0\nB1 R\n_ _\n===\n_ _\n_ _\n";
+        assert_eq!(run_suite(content, &[])[0].outcome(), Outcome::Failed);
+        let results = run_suite(content, &[Rule::DestroyRocks]);
+        assert_eq!(results[0].outcome(), Outcome::Passed);
+    }
+}
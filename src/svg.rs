@@ -0,0 +1,249 @@
+use std::fmt::Write;
+
+use crate::board::Board;
+use crate::constants::{DOWN, LEFT, RIGHT, UP};
+use crate::coordinate::Coordinate;
+use crate::detonation::{DetonationReport, Ray};
+use crate::item::Item;
+use crate::xml;
+
+/// Lado de cada casilla, en pixeles.
+const CELL: u32 = 40;
+/// Margen alrededor del tablero, en pixeles.
+const MARGIN: u32 = 10;
+const RAY_COLOUR: &str = "#ff8c00";
+
+/// Dibuja el tablero como una imagen SVG.
+///
+/// Cada tipo de item tiene su propio simbolo y color, y los enemigos muestran su vida. Si se
+/// pasa el resultado de una detonacion, se dibujan encima las rafagas (con sus giros en los
+/// desvios) y el orden en que explotaron las bombas de la cadena.
+///
+/// # Argumentos
+///
+/// * `board`: El tablero a dibujar.
+/// * `report`: La detonacion a dibujar sobre el tablero, si la hay.
+/// * `title`: Titulo de la imagen, si lo hay.
+pub fn render(board: &Board, report: Option<&DetonationReport>, title: Option<&str>) -> String {
+    let side = board.dimension() * CELL + 2 * MARGIN;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{side}" height="{side}" viewBox="0 0 {side} {side}" font-family="monospace" font-size="12">"#
+    );
+    if let Some(title) = title {
+        let _ = writeln!(svg, "<title>{}</title>", xml::escape(title));
+    }
+    let _ = writeln!(
+        svg,
+        r##"<rect x="0" y="0" width="{side}" height="{side}" fill="#ffffff"/>"##
+    );
+
+    draw_grid(&mut svg, board.dimension());
+    for x in 0..board.dimension() {
+        for y in 0..board.dimension() {
+            draw_item(&mut svg, board.get(x, y), x, y);
+        }
+    }
+    if let Some(report) = report {
+        for ray in &report.rays {
+            draw_ray(&mut svg, ray);
+        }
+        for (i, bomb) in report.chain.iter().enumerate() {
+            draw_chain_label(&mut svg, bomb, i + 1);
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Esquina superior izquierda de la casilla de la fila `x` y columna `y`.
+fn corner(x: u32, y: u32) -> (u32, u32) {
+    (MARGIN + y * CELL, MARGIN + x * CELL)
+}
+
+/// Centro de la casilla de la fila `x` y columna `y`.
+fn center(x: u32, y: u32) -> (u32, u32) {
+    let (left, top) = corner(x, y);
+    (left + CELL / 2, top + CELL / 2)
+}
+
+fn draw_grid(svg: &mut String, dimension: u32) {
+    let end = MARGIN + dimension * CELL;
+    let _ = writeln!(svg, r##"<g stroke="#cccccc" stroke-width="1">"##);
+    for i in 0..=dimension {
+        let offset = MARGIN + i * CELL;
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{offset}" y1="{MARGIN}" x2="{offset}" y2="{end}"/>"#
+        );
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{MARGIN}" y1="{offset}" x2="{end}" y2="{offset}"/>"#
+        );
+    }
+    svg.push_str("</g>\n");
+}
+
+fn draw_item(svg: &mut String, item: Item, x: u32, y: u32) {
+    let (left, top) = corner(x, y);
+    let (cx, cy) = center(x, y);
+    let radius = CELL / 2 - 6;
+    match item {
+        Item::Enemy(life) => {
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{cx}" cy="{cy}" r="{radius}" fill="#d62728"/>"##
+            );
+            draw_label(svg, cx, cy, &format!("F{}", life), "#ffffff");
+        }
+        Item::NormalBomb(range) => {
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{cx}" cy="{cy}" r="{radius}" fill="#222222"/>"##
+            );
+            draw_label(svg, cx, cy, &format!("B{}", range), "#ffffff");
+        }
+        Item::TransferBomb(range) => {
+            let _ = writeln!(
+                svg,
+                r##"<circle cx="{cx}" cy="{cy}" r="{radius}" fill="#7b3fbf"/>"##
+            );
+            draw_label(svg, cx, cy, &format!("S{}", range), "#ffffff");
+        }
+        Item::Rock => {
+            let _ = writeln!(
+                svg,
+                r##"<polygon points="{},{} {},{} {},{} {},{}" fill="#8c8c8c"/>"##,
+                left + 6,
+                top + CELL - 6,
+                left + 12,
+                top + 10,
+                left + CELL - 10,
+                top + 6,
+                left + CELL - 6,
+                top + CELL - 6
+            );
+        }
+        Item::Wall => {
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#5a3e2b"/>"##,
+                left + 2,
+                top + 2,
+                CELL - 4,
+                CELL - 4
+            );
+        }
+        Item::Deflection(direction) => {
+            let _ = writeln!(
+                svg,
+                r##"<polygon points="{}" fill="#e6b800"/>"##,
+                arrow(cx, cy, direction)
+            );
+        }
+        Item::Empty => (),
+    }
+}
+
+/// Puntos de un triangulo centrado en (`cx`, `cy`) que apunta hacia `direction`.
+fn arrow(cx: u32, cy: u32, direction: char) -> String {
+    let d = CELL / 2 - 6;
+    let points = match direction {
+        UP => [(cx, cy - d), (cx - d, cy + d), (cx + d, cy + d)],
+        DOWN => [(cx, cy + d), (cx - d, cy - d), (cx + d, cy - d)],
+        LEFT => [(cx - d, cy), (cx + d, cy - d), (cx + d, cy + d)],
+        RIGHT => [(cx + d, cy), (cx - d, cy - d), (cx - d, cy + d)],
+        _ => [(cx, cy), (cx, cy), (cx, cy)],
+    };
+    let points: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    points.join(" ")
+}
+
+fn draw_label(svg: &mut String, cx: u32, cy: u32, text: &str, colour: &str) {
+    let _ = writeln!(
+        svg,
+        r#"<text x="{cx}" y="{}" text-anchor="middle" fill="{colour}">{}</text>"#,
+        cy + 4,
+        xml::escape(text)
+    );
+}
+
+/// Dibuja el recorrido de la rafaga como una linea que une los centros de las casillas
+/// alcanzadas, marcando con un punto cada casilla donde cambia de direccion.
+fn draw_ray(svg: &mut String, ray: &Ray) {
+    if ray.steps.len() < 2 {
+        return;
+    }
+    let points: Vec<String> = ray
+        .steps
+        .iter()
+        .map(|step| {
+            let (cx, cy) = center(step.coordinate.x, step.coordinate.y);
+            format!("{},{}", cx, cy)
+        })
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="{RAY_COLOUR}" stroke-width="4" stroke-opacity="0.6" stroke-linecap="round"/>"#,
+        points.join(" ")
+    );
+    for pair in ray.steps.windows(2) {
+        if pair[0].direction != pair[1].direction {
+            let (cx, cy) = center(pair[0].coordinate.x, pair[0].coordinate.y);
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{cx}" cy="{cy}" r="5" fill="{RAY_COLOUR}"/>"#
+            );
+        }
+    }
+}
+
+/// Numera la bomba con su posicion en la cadena de explosiones, en la esquina de su casilla.
+fn draw_chain_label(svg: &mut String, bomb: &Coordinate, order: usize) {
+    let (left, top) = corner(bomb.x, bomb.y);
+    let _ = writeln!(
+        svg,
+        r##"<circle cx="{}" cy="{}" r="7" fill="#1f77b4"/>"##,
+        left + 8,
+        top + 8
+    );
+    let _ = writeln!(
+        svg,
+        r##"<text x="{}" y="{}" text-anchor="middle" font-size="10" fill="#ffffff">{}</text>"##,
+        left + 8,
+        top + 11,
+        order
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_render_board() {
+        let game = Game::from_file("./tests/inputs/deviation_down.txt").unwrap();
+        let svg = render(game.board(), None, Some("desvio <abajo>"));
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<title>desvio &lt;abajo&gt;</title>"));
+        assert!(svg.contains(">F1</text>"));
+        assert!(!svg.contains("<polyline"));
+    }
+
+    #[test]
+    fn test_render_blast_with_turns_and_chain() {
+        let game = Game::from_file("./tests/inputs/catedra_3.txt").unwrap();
+        let (_, report) = game.simulate(4, 0).unwrap();
+        let svg = render(game.board(), Some(&report), None);
+        assert!(svg.contains("<polyline"));
+        // El desvio DU hace girar la rafaga en la casilla (4, 4).
+        let (cx, cy) = center(4, 4);
+        assert!(svg.contains(&format!(r#"<circle cx="{cx}" cy="{cy}" r="5""#)));
+        for order in 1..=report.chain.len() {
+            assert!(svg.contains(&format!(">{}</text>", order)));
+        }
+    }
+}
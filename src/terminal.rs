@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::board::Board;
+use crate::coordinate::Coordinate;
+use crate::detonation::DetonationReport;
+use crate::i18n;
+use crate::item::Item;
+
+const RESET: &str = "\x1b[0m";
+/// Fondo de las casillas alcanzadas por la detonacion.
+const BLAST_BACKGROUND: &str = "43";
+/// Marca de las casillas alcanzadas cuando no se usan colores.
+const BLAST_MARK: &str = "*";
+
+/// Arma la vista de terminal de una detonacion: el tablero inicial a la izquierda y el final a la
+/// derecha, separados por ` | `, seguidos del orden de la cadena de explosiones.
+///
+/// En el tablero inicial, cada bomba de la cadena se marca con su orden (`B2:1`) y las casillas
+/// alcanzadas por alguna rafaga se resaltan con fondo amarillo, o con `*` si no se usan colores.
+///
+/// # Argumentos
+///
+/// * `before`: El tablero antes de la detonacion.
+/// * `after`: El tablero despues de la detonacion.
+/// * `report`: El resultado de la detonacion.
+/// * `colour`: Si es `true` se usan colores ANSI; si no, texto plano.
+pub fn render(before: &Board, after: &Board, report: &DetonationReport, colour: bool) -> String {
+    let order: HashMap<Coordinate, usize> = report
+        .chain
+        .iter()
+        .enumerate()
+        .map(|(i, bomb)| (*bomb, i + 1))
+        .collect();
+
+    let left = cells(before, |coordinate, item| {
+        let mut text = item.to_string();
+        if let Some(order) = order.get(coordinate) {
+            text.push_str(&format!(":{}", order));
+        }
+        let reached = report.affected.contains_key(coordinate);
+        if reached && !colour {
+            text.push_str(BLAST_MARK);
+        }
+        (text, reached)
+    });
+    let right = cells(after, |_, item| (item.to_string(), false));
+    let width = left
+        .iter()
+        .chain(right.iter())
+        .flatten()
+        .map(|(_, text, _)| text.chars().count())
+        .max()
+        .unwrap_or(1);
+
+    let style = |item: &Item, text: &str, reached: bool| {
+        let padding = " ".repeat(width - text.chars().count());
+        if colour {
+            format!("{}{}", paint(item, text, reached), padding)
+        } else {
+            format!("{}{}", text, padding)
+        }
+    };
+    let board_width = ((width + 1) * before.dimension() as usize).saturating_sub(1);
+
+    let mut view = format!(
+        "{:<board_width$} | {}\n",
+        i18n::VIEW_BEFORE.text(),
+        i18n::VIEW_AFTER.text()
+    );
+    for i in 0..left.len().max(right.len()) {
+        let row = |rows: &Vec<Vec<(Item, String, bool)>>| match rows.get(i) {
+            Some(row) => row
+                .iter()
+                .map(|(item, text, reached)| style(item, text, *reached))
+                .collect::<Vec<String>>()
+                .join(" "),
+            None => " ".repeat(board_width),
+        };
+        let line = format!("{} | {}", row(&left), row(&right));
+        view.push_str(line.trim_end());
+        view.push('\n');
+    }
+
+    let chain: Vec<String> = report
+        .chain
+        .iter()
+        .enumerate()
+        .map(|(i, bomb)| format!("{} {}", i + 1, bomb))
+        .collect();
+    view.push_str(&i18n::VIEW_CHAIN.fill(&[&chain.join(" -> ")]));
+    view.push('\n');
+    view
+}
+
+/// Devuelve, para cada fila del tablero, el item, el texto y si la casilla esta resaltada.
+fn cells<F>(board: &Board, describe: F) -> Vec<Vec<(Item, String, bool)>>
+where
+    F: Fn(&Coordinate, Item) -> (String, bool),
+{
+    (0..board.dimension())
+        .map(|x| {
+            (0..board.dimension())
+                .map(|y| {
+                    let coordinate = board.coordinate(x, y);
+                    let item = board.get(x, y);
+                    let (text, reached) = describe(&coordinate, item);
+                    (item, text, reached)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Codigo ANSI del color de cada item: paredes y rocas en gris y amarillo, enemigos de verde a
+/// rojo segun su vida y bombas segun su tipo.
+fn item_colour(item: &Item) -> &str {
+    match item {
+        Item::Wall => "90",
+        Item::Rock => "33",
+        Item::Enemy(1) => "92",
+        Item::Enemy(2) => "93",
+        Item::Enemy(_) => "91",
+        Item::NormalBomb(_) => "1;96",
+        Item::TransferBomb(_) => "1;95",
+        Item::Deflection(_) => "94",
+        Item::Empty => "2",
+    }
+}
+
+fn paint(item: &Item, text: &str, reached: bool) -> String {
+    if reached {
+        format!(
+            "\x1b[{};{}m{}{}",
+            item_colour(item),
+            BLAST_BACKGROUND,
+            text,
+            RESET
+        )
+    } else {
+        format!("\x1b[{}m{}{}", item_colour(item), text, RESET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_render_plain() {
+        let game = Game::from_file("./tests/inputs/deviation_down.txt").unwrap();
+        let (board, report) = game.simulate(0, 1).unwrap();
+        assert_eq!(
+            render(game.board(), &board, &report, false),
+            "Inicial           | Final\n\
+             R*    B2:1* DD*   | R     _     DD\n\
+             _     W*    F1*   | _     W     _\n\
+             R     _     W     | R     _     W\n\
+             Cadena: 1 (1, 0)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_colour() {
+        let game = Game::from_file("./tests/inputs/deviation_down.txt").unwrap();
+        let (board, report) = game.simulate(0, 1).unwrap();
+        let view = render(game.board(), &board, &report, true);
+        assert!(view.contains("\x1b[1;96;43mB2:1\x1b[0m"));
+        assert!(view.contains("\x1b[92;43mF1\x1b[0m"));
+        assert!(!view.contains(BLAST_MARK));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::coordinate::Coordinate;
+use crate::detonation::TraceEvent;
+use crate::format::LineFormat;
+use crate::json::JsonValue;
+
+/// Nombre del evento en el registro. No depende del idioma de los mensajes.
+pub fn event_name(event: &TraceEvent) -> &'static str {
+    match event {
+        TraceEvent::BombDetonated { .. } => "bomb_detonated",
+        TraceEvent::RayStarted { .. } => "ray_started",
+        TraceEvent::CellEntered { .. } => "cell_entered",
+        TraceEvent::Deflected { .. } => "deflected",
+        TraceEvent::Blocked { .. } => "blocked",
+        TraceEvent::EnemyDamaged { .. } => "enemy_damaged",
+        TraceEvent::ChainedBomb { .. } => "chained_bomb",
+    }
+}
+
+/// Campos del evento, en orden. Las coordenadas se separan en fila y columna; la de la bomba que
+/// detona otra en cadena usa las claves `by_row` y `by_column`.
+fn fields(event: &TraceEvent) -> Vec<(&'static str, JsonValue)> {
+    let number = |n: u32| JsonValue::Number(n as f64);
+    let text = |value: &dyn ToString| JsonValue::String(value.to_string());
+    let cell = |coordinate: &Coordinate| {
+        vec![
+            ("row", number(coordinate.x)),
+            ("column", number(coordinate.y)),
+        ]
+    };
+    let mut fields = match event {
+        TraceEvent::BombDetonated { bomb, .. } => cell(bomb),
+        TraceEvent::RayStarted { origin, .. } => cell(origin),
+        TraceEvent::CellEntered { coordinate, .. }
+        | TraceEvent::Deflected { coordinate, .. }
+        | TraceEvent::Blocked { coordinate, .. }
+        | TraceEvent::EnemyDamaged { coordinate, .. } => cell(coordinate),
+        TraceEvent::ChainedBomb { bomb, .. } => cell(bomb),
+    };
+    match event {
+        TraceEvent::BombDetonated { item, .. } | TraceEvent::Blocked { item, .. } => {
+            fields.push(("item", text(item)));
+        }
+        TraceEvent::RayStarted {
+            direction, range, ..
+        } => {
+            fields.push(("direction", text(direction)));
+            fields.push(("range", number(*range)));
+        }
+        TraceEvent::CellEntered {
+            direction,
+            remaining,
+            ..
+        } => {
+            fields.push(("direction", text(direction)));
+            fields.push(("remaining", number(*remaining)));
+        }
+        TraceEvent::Deflected { from, to, .. } => {
+            fields.push(("from", text(from)));
+            fields.push(("to", text(to)));
+        }
+        TraceEvent::EnemyDamaged { item, hits, .. } => {
+            fields.push(("item", text(item)));
+            fields.push(("hits", number(*hits)));
+        }
+        TraceEvent::ChainedBomb { item, by, .. } => {
+            fields.push(("item", text(item)));
+            fields.push(("by_row", number(by.x)));
+            fields.push(("by_column", number(by.y)));
+        }
+    }
+    fields
+}
+
+/// Escribe el evento como una linea de texto: su nombre seguido de sus campos como `clave=valor`.
+pub fn to_text(event: &TraceEvent) -> String {
+    let mut line = event_name(event).to_string();
+    for (key, value) in fields(event) {
+        let value = match value {
+            JsonValue::String(value) => value,
+            value => value.to_string(),
+        };
+        line.push_str(&format!(" {}={}", key, value));
+    }
+    line
+}
+
+/// Representa el evento como objeto JSON, con su nombre en la clave `event` y sus campos.
+pub fn to_json(event: &TraceEvent) -> JsonValue {
+    let mut pairs = vec![("event", JsonValue::String(event_name(event).to_string()))];
+    pairs.extend(fields(event));
+    JsonValue::object(pairs)
+}
+
+/// Arma el registro completo de una detonacion, con una linea por evento en el formato pedido.
+pub fn render(events: &[TraceEvent], format: LineFormat) -> String {
+    let mut text = String::new();
+    for event in events {
+        match format {
+            LineFormat::Text => text.push_str(&to_text(event)),
+            LineFormat::Json => text.push_str(&to_json(event).to_string()),
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_render_deflection_trace() {
+        let game = Game::from_file("./tests/inputs/deviation_down.txt").unwrap();
+        let (_, report) = game.simulate(0, 1).unwrap();
+        let trace = render(&report.trace, LineFormat::Text);
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines[0], "bomb_detonated row=0 column=1 item=B2");
+        assert_eq!(lines[1], "ray_started row=0 column=1 direction=R range=2");
+        assert_eq!(
+            lines[2],
+            "cell_entered row=0 column=1 direction=R remaining=2"
+        );
+        assert_eq!(
+            lines[3],
+            "cell_entered row=0 column=2 direction=R remaining=1"
+        );
+        assert_eq!(lines[4], "deflected row=0 column=2 from=R to=D");
+        assert_eq!(
+            lines[5],
+            "cell_entered row=1 column=2 direction=D remaining=0"
+        );
+        assert_eq!(lines[6], "enemy_damaged row=1 column=2 item=F1 hits=1");
+        assert!(lines.contains(&"blocked row=1 column=1 item=W"));
+    }
+
+    #[test]
+    fn test_render_json_lines() {
+        let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+        let (_, report) = game.simulate(0, 0).unwrap();
+        let trace = render(&report.trace, LineFormat::Json);
+        assert!(trace.lines().all(|line| crate::json::parse(line).is_ok()));
+        assert!(trace.contains(
+            "{\"event\":\"chained_bomb\",\"row\":2,\"column\":0,\"item\":\"B5\",\"by_row\":0,\"by_column\":0}\n"
+        ));
+    }
+}
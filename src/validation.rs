@@ -0,0 +1,122 @@
+use crate::error::BombermanError;
+use crate::file_io;
+use crate::game;
+use crate::header::Header;
+
+/// Revisa el texto completo de un tablero y devuelve todos los problemas que encuentra, en el
+/// orden en que aparecen, en lugar de detenerse en el primero como `Game::from_text`.
+///
+/// Se informan los items invalidos (no reconocidos, con alcance o direccion mal escritos, o
+/// enemigos con mas vida que `MAX_LIFE`) con su ubicacion, las filas cuya cantidad de casillas
+/// difiere de la primera, un encabezado invalido y, si todas las filas tienen el mismo ancho, un
+/// tablero que no es cuadrado. Las lineas de comentario se ignoran. Un tablero en formato
+/// comprimido se expande antes de revisarlo; si no se puede expandir o tiene un item invalido,
+/// solo se informa el primer error.
+///
+/// Devuelve una lista vacia si el tablero es valido.
+pub fn validate(content: &str) -> Vec<BombermanError> {
+    let content = match file_io::board_text(content) {
+        Ok(content) => content,
+        Err(e) => return vec![e],
+    };
+    let content = content.as_ref();
+    let mut errors = Vec::new();
+    if let Err(e) = Header::parse(content) {
+        errors.push(e);
+    }
+    let mut expected_columns: Option<usize> = None;
+    let mut rows = 0;
+
+    for (row, (line_number, line)) in file_io::board_lines(content).enumerate() {
+        let items = game::parse_line(line, row);
+        match expected_columns {
+            None => expected_columns = Some(items.len()),
+            Some(expected) if expected != items.len() => {
+                errors.push(BombermanError::InvalidRowWidth {
+                    line: line_number,
+                    expected,
+                    found: items.len(),
+                });
+            }
+            _ => (),
+        }
+        errors.extend(
+            items
+                .into_iter()
+                .filter_map(Result::err)
+                .map(|e| e.at_line(line_number)),
+        );
+        rows += 1;
+    }
+
+    let width_errors = errors
+        .iter()
+        .any(|e| matches!(e, BombermanError::InvalidRowWidth { .. }));
+    match expected_columns {
+        None => errors.push(BombermanError::EmptyFileError),
+        Some(columns) if columns != rows && !width_errors => {
+            errors.push(BombermanError::NonSquareBoardError)
+        }
+        _ => (),
+    }
+    errors
+}
+
+/// Igual que `validate`, leyendo el tablero de `path` (o `-` para la entrada estándar).
+///
+/// # Errores
+///
+/// Devuelve `BombermanError::InputPathError` si no se puede leer el archivo.
+pub fn validate_file(path: &str) -> Result<Vec<BombermanError>, BombermanError> {
+    let content = file_io::read_to_string(path)?;
+    Ok(validate(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_board() {
+        assert_eq!(validate_file("./tests/inputs/catedra_1.txt"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_validate_collects_every_error() {
+        let errors = validate("B1 Z _\nF4 _\n_ BX DQ\n");
+        let codes: Vec<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(
+            codes,
+            vec![
+                "invalid_item",
+                "invalid_row_width",
+                "invalid_enemy_life",
+                "invalid_normal_bomb_format",
+                "invalid_deflection_format",
+            ]
+        );
+        assert_eq!(
+            errors[1].message(),
+            "La fila no tiene la misma cantidad de casillas que la primera (linea 2: 2 casillas, se esperaban 3)"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_file_lines_with_comments() {
+        let errors = validate("# title: prueba\n# comentario\nB1 _\n_ X\n");
+        assert_eq!(
+            errors[0].message(),
+            "Item no reconocido (linea 4, columna 3, item 'X')"
+        );
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_non_square_and_empty() {
+        assert_eq!(
+            validate("_ _\n_ _\n_ _\n"),
+            vec![BombermanError::NonSquareBoardError]
+        );
+        assert_eq!(validate(""), vec![BombermanError::EmptyFileError]);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::config;
+use crate::error::BombermanError;
+use crate::game::Game;
+use crate::header::Rule;
+use crate::i18n;
+use crate::terminal;
+
+/// Secuencia ANSI que borra la terminal y lleva el cursor al principio.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Observa la fecha de modificacion de un archivo para detectar cuando se guarda.
+///
+/// # Campos
+///
+/// * `path`: Ruta del archivo observado.
+/// * `last`: Fecha de modificacion en la ultima consulta, o `None` si el archivo no existia.
+/// * `checked`: Si es `true` ya se consulto al menos una vez.
+#[derive(Debug)]
+pub struct Watcher {
+    path: PathBuf,
+    last: Option<SystemTime>,
+    checked: bool,
+}
+
+impl Watcher {
+    /// Crea un observador para el archivo en `path`.
+    pub fn new(path: &str) -> Watcher {
+        Watcher {
+            path: PathBuf::from(path),
+            last: None,
+            checked: false,
+        }
+    }
+
+    /// Indica si el archivo cambio (se modifico, se creo o se borro) desde la ultima consulta.
+    /// La primera consulta siempre devuelve `true`, para mostrar el estado inicial.
+    pub fn changed(&mut self) -> bool {
+        let modified = fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let changed = !self.checked || modified != self.last;
+        self.checked = true;
+        self.last = modified;
+        changed
+    }
+}
+
+/// Arma la pantalla del modo observador: lee el tablero en `path`, detona la bomba indicada (o
+/// la del encabezado `# detonate:` si no se indica ninguna) y devuelve la vista de terminal.
+///
+/// Los errores no interrumpen la observacion: se devuelven como texto en lugar de la vista,
+/// para que se vean hasta el proximo guardado.
+///
+/// # Argumentos
+///
+/// * `path`: Ruta del tablero.
+/// * `bomb`: Fila y columna de la bomba a detonar, o `None` para usar la del encabezado.
+/// * `rules`: Reglas que se agregan a las del encabezado del tablero.
+/// * `colour`: Si es `true` se usan colores ANSI; si no, texto plano.
+pub fn frame(path: &str, bomb: Option<(u32, u32)>, rules: &[Rule], colour: bool) -> String {
+    let mut text = i18n::WATCH_HEADER.fill(&[&path]);
+    text.push_str("\n\n");
+    let result = Game::from_file(path).and_then(|game| {
+        let game = game.with_rules(rules);
+        let (x, y) = match (bomb, game.header().detonate) {
+            (Some(bomb), _) => bomb,
+            (None, Some((x, y))) => config::cell(x, y)?,
+            (None, None) => return Err(BombermanError::MissingDetonation),
+        };
+        let (board, report) = game.simulate(x, y)?;
+        Ok((game, board, report))
+    });
+    match result {
+        Ok((game, board, report)) => {
+            for warning in game.warnings() {
+                text.push_str(&i18n::WARNING.fill(&[warning]));
+                text.push('\n');
+            }
+            text.push_str(&terminal::render(game.board(), &board, &report, colour));
+        }
+        Err(e) => text.push_str(&format!("Error: {}\n", e.message())),
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn test_watcher_detects_changes() {
+        let path = std::env::temp_dir().join(format!("bomberman_watch_{}.txt", std::process::id()));
+        let name = path.to_string_lossy().into_owned();
+        let mut watcher = Watcher::new(&name);
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        fs::write(&path, "B1 _\n_ F1\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        let later = SystemTime::now() + Duration::from_secs(5);
+        File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(later))
+            .unwrap();
+        assert!(watcher.changed());
+        fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn test_frame_shows_view_or_error() {
+        let view = frame(
+            "./tests/inputs/deviation_down.txt",
+            Some((0, 1)),
+            &[],
+            false,
+        );
+        assert!(view.contains("R*    B2:1* DD*   | R     _     DD\n"));
+
+        let error = frame("./tests/inputs/catedra_1.txt", Some((0, 1)), &[], false);
+        assert!(error.ends_with(&format!(
+            "Error: {}\n",
+            BombermanError::InvalidBombCoordinate.message()
+        )));
+        let missing = frame("./tests/inputs/catedra_1.txt", None, &[], false);
+        assert!(missing.ends_with(&format!(
+            "Error: {}\n",
+            BombermanError::MissingDetonation.message()
+        )));
+    }
+}
@@ -0,0 +1,24 @@
+/// Reemplaza los caracteres especiales de XML (`&`, `<`, `>` y `"`) por sus entidades, para
+/// escribir `text` dentro de un elemento o de un atributo entre comillas dobles.
+///
+/// Se usa tanto en las imagenes SVG como en los reportes JUnit.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<a href=\"x\">B&W</a>"),
+            "&lt;a href=&quot;x&quot;&gt;B&amp;W&lt;/a&gt;"
+        );
+        assert_eq!(escape("sin cambios"), "sin cambios");
+    }
+}
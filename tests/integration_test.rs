@@ -1,32 +1,49 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
 use std::{fs::File, io::Read};
 
-use bomberman::{config::Config, error::BombermanError, game::Game};
+use bomberman::{config::Config, file_io, format, game::Game};
 
-fn simulator_game(
-    name_input: String,
-    path_output: String,
-    x: usize,
-    y: usize,
-) -> Result<(), BombermanError> {
+/// Ejecutable del programa, sin la variable `LANG` para que los mensajes salgan en español.
+fn bomberman() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_bomberman"));
+    command.env_remove("LANG");
+    command
+}
+
+fn simulator_game(name_input: String, path_output: String, x: usize, y: usize) {
     let conf = Config {
         name_input,
         path_output: path_output.clone(),
-        x,
-        y,
+        x: Some(x),
+        y: Some(y),
+        ..Config::default()
     };
     let mut game = match Game::new(&conf) {
         Ok(game) => game,
-        Err(e) => return Ok(e.send(path_output)),
+        Err(e) => return e.send(path_output),
     };
 
-    match game.denotate_bomb(conf.x as u32, conf.y as u32) {
+    match game.denotate_bomb(x as u32, y as u32) {
         Ok(_) => (),
-        Err(e) => return Ok(e.send(path_output)),
+        Err(e) => return e.send(path_output),
     };
 
-    match game.save_game(&conf.path_output) {
-        Ok(_) => Ok(()),
-        Err(e) => return Ok(e.send(path_output)),
+    if let Err(e) = game.save_game(&conf.path_output) {
+        e.send(path_output)
+    }
+}
+
+fn simulator_game_json(name_input: String, path_output: String, x: usize, y: usize) {
+    let result = file_io::read_to_string(&name_input)
+        .and_then(|text| format::board_from_json(&text))
+        .and_then(|board| Game::from_board(board).simulate(x as u32, y as u32));
+    match result {
+        Ok((board, report)) => {
+            let result = format!("{}\n", format::result_to_json(&board, &report));
+            std::fs::write(&path_output, result).unwrap();
+        }
+        Err(e) => e.send_json(path_output),
     }
 }
 
@@ -60,7 +77,7 @@ fn test_input_file_not_found() {
     let name_input = "file_not_found.txt".to_string();
     let path_output = "./tests/outputs/file_not_found.txt".to_string();
     let path_expected_output = "./tests/expected_output/file_not_found.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 #[test]
@@ -68,7 +85,7 @@ fn test_no_bomb_at_coordinates() {
     let name_input = "./tests/inputs/no_bomb_at_coordinates.txt".to_string();
     let path_output = "./tests/outputs/no_bomb_at_coordinates.txt".to_string();
     let path_expected_output = "./tests/expected_output/no_bomb_at_coordinates.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -77,7 +94,7 @@ fn test_unrecognized_item() {
     let name_input = "./tests/inputs/unrecognized_item.txt".to_string();
     let path_output = "./tests/outputs/unrecognized_item.txt".to_string();
     let path_expected_output = "./tests/expected_output/unrecognized_item.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -86,7 +103,7 @@ fn test_non_square_matrix() {
     let name_input = "./tests/inputs/non_square_matrix.txt".to_string();
     let path_output = "./tests/outputs/non_square_matrix.txt".to_string();
     let path_expected_output = "./tests/expected_output/non_square_matrix.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -95,7 +112,7 @@ fn test_catedra_1() {
     let name_input = "./tests/inputs/catedra_1.txt".to_string();
     let path_output = "./tests/outputs/catedra_1.txt".to_string();
     let path_expected_output = "./tests/expected_output/catedra_1.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -104,7 +121,7 @@ fn test_catedra_2() {
     let name_input = "./tests/inputs/catedra_2.txt".to_string();
     let path_output = "./tests/outputs/catedra_2.txt".to_string();
     let path_expected_output = "./tests/expected_output/catedra_2.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 4, 2);
+    simulator_game(name_input.clone(), path_output.clone(), 4, 2);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -113,7 +130,7 @@ fn test_catedra_3() {
     let name_input = "./tests/inputs/catedra_3.txt".to_string();
     let path_output = "./tests/outputs/catedra_3.txt".to_string();
     let path_expected_output = "./tests/expected_output/catedra_3.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 4, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 4, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -122,7 +139,7 @@ fn test_down_deviation() {
     let name_input = "./tests/inputs/deviation_down.txt".to_string();
     let path_output = "./tests/outputs/deviation_down.txt".to_string();
     let path_expected_output = "./tests/expected_output/deviation_down.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 1);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 1);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -131,7 +148,7 @@ fn test_up_deviation() {
     let name_input = "./tests/inputs/deviation_up.txt".to_string();
     let path_output = "./tests/outputs/deviation_up.txt".to_string();
     let path_expected_output = "./tests/expected_output/deviation_up.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 2, 1);
+    simulator_game(name_input.clone(), path_output.clone(), 2, 1);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -140,7 +157,7 @@ fn test_right_deviation() {
     let name_input = "./tests/inputs/deviation_right.txt".to_string();
     let path_output = "./tests/outputs/deviation_right.txt".to_string();
     let path_expected_output = "./tests/expected_output/deviation_right.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 1);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 1);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -149,7 +166,7 @@ fn test_left_deviation() {
     let name_input = "./tests/inputs/deviation_left.txt".to_string();
     let path_output = "./tests/outputs/deviation_left.txt".to_string();
     let path_expected_output = "./tests/expected_output/deviation_left.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 1);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 1);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -158,7 +175,7 @@ fn test_transfer_bomb() {
     let name_input = "./tests/inputs/transfer_bomb.txt".to_string();
     let path_output = "./tests/outputs/transfer_bomb.txt".to_string();
     let path_expected_output = "./tests/expected_output/transfer_bomb.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
 
@@ -167,6 +184,612 @@ fn test_max_enemy_life() {
     let name_input = "./tests/inputs/max_enemy_life.txt".to_string();
     let path_output = "./tests/outputs/max_enemy_life.txt".to_string();
     let path_expected_output = "./tests/expected_output/max_enemy_life.txt".to_string();
-    let _ = simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    simulator_game(name_input.clone(), path_output.clone(), 0, 0);
+    assert!(compare_files(&path_output, &path_expected_output));
+}
+
+#[test]
+fn test_simulate_keeps_game_unchanged() {
+    let game = Game::from_file("./tests/inputs/catedra_1.txt").unwrap();
+    let original = game.board().clone();
+
+    let (board, report) = game.simulate(0, 0).unwrap();
+    let (again, _) = game.simulate(0, 0).unwrap();
+
+    assert_eq!(game.board(), &original);
+    assert_eq!(board, again);
+    assert_eq!(report.chain.len(), 3);
+    assert_eq!(board.get(0, 4), bomberman::item::Item::Empty);
+}
+
+#[test]
+fn test_json_deviation_down() {
+    let name_input = "./tests/inputs/deviation_down.json".to_string();
+    let path_output = "./tests/outputs/deviation_down.json".to_string();
+    let path_expected_output = "./tests/expected_output/deviation_down.json".to_string();
+    simulator_game_json(name_input.clone(), path_output.clone(), 0, 1);
+    assert!(compare_files(&path_output, &path_expected_output));
+}
+
+#[test]
+fn test_json_max_enemy_life() {
+    let name_input = "./tests/inputs/max_enemy_life.json".to_string();
+    let path_output = "./tests/outputs/max_enemy_life.json".to_string();
+    let path_expected_output = "./tests/expected_output/max_enemy_life.json".to_string();
+    simulator_game_json(name_input.clone(), path_output.clone(), 0, 0);
     assert!(compare_files(&path_output, &path_expected_output));
 }
+
+fn run_binary_with_stdin(args: &[&str], input: &[u8]) -> std::process::Output {
+    let mut child = bomberman()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_stdin_to_stdout() {
+    let input = std::fs::read("./tests/inputs/catedra_2.txt").unwrap();
+    let expected = std::fs::read("./tests/expected_output/catedra_2.txt").unwrap();
+    let output = run_binary_with_stdin(&["-", "-", "2", "4"], &input);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, expected);
+}
+
+#[test]
+fn test_stdin_error_goes_to_stderr() {
+    let output = run_binary_with_stdin(&["-", "-", "0", "0"], b"B1 Z\n_ W\n");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        output.stderr,
+        "Error: Item no reconocido (linea 1, columna 4, item 'Z')\n".as_bytes()
+    );
+}
+
+#[test]
+fn test_validate_lists_every_error() {
+    let output = bomberman()
+        .args(["validate", "./tests/inputs/many_errors.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    let expected = std::fs::read("./tests/expected_output/many_errors.txt").unwrap();
+    assert_eq!(output.stderr, expected);
+    assert!(output.stdout.is_empty());
+
+    let output = bomberman()
+        .args(["--error-format", "json", "validate"])
+        .arg("./tests/inputs/many_errors.txt")
+        .output()
+        .unwrap();
+    let diagnostics = String::from_utf8(output.stderr).unwrap();
+    assert!(diagnostics
+        .lines()
+        .all(|line| line.starts_with("{\"error\":{\"code\":")));
+}
+
+#[test]
+fn test_header_detonate_and_rules() {
+    let output = bomberman()
+        .args(["./tests/inputs/header_destroy_rocks.txt", "-"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let expected = std::fs::read("./tests/expected_output/header_destroy_rocks.txt").unwrap();
+    assert_eq!(output.stdout, expected);
+}
+
+#[test]
+fn test_suite_combined_result() {
+    let output = bomberman()
+        .args([
+            "suite",
+            "./tests/inputs/suite.txt",
+            "./tests/outputs/suite.txt",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(compare_files(
+        "./tests/outputs/suite.txt",
+        "./tests/expected_output/suite.txt"
+    ));
+}
+
+#[test]
+fn test_run_length_input_plain_output() {
+    let output = bomberman()
+        .args(["./tests/inputs/catedra_2_rle.txt", "-", "2", "4"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let expected = std::fs::read("./tests/expected_output/catedra_2.txt").unwrap();
+    assert_eq!(output.stdout, expected);
+}
+
+#[test]
+fn test_plain_input_run_length_output() {
+    let output = bomberman()
+        .args([
+            "--format",
+            "rle",
+            "./tests/inputs/catedra_2.txt",
+            "-",
+            "2",
+            "4",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let expected = std::fs::read("./tests/expected_output/catedra_2_rle.txt").unwrap();
+    assert_eq!(output.stdout, expected);
+}
+
+#[test]
+fn test_normalized_input_warnings() {
+    let output = bomberman()
+        .args(["./tests/inputs/windows_tabs.txt", "-", "0", "0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"_ _ F1\n_ W _\n_ _ _\n");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.lines().count(), 3);
+    assert!(stderr.contains("se ignoraron 1 linea(s) vacia(s) al final del archivo"));
+}
+
+#[test]
+fn test_strict_keeps_trailing_blank_line_as_row() {
+    let output = bomberman()
+        .args(["--strict", "./tests/inputs/windows_tabs.txt", "-", "0", "0"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(output.stderr, b"Error: El tablero no es cuadrado\n");
+}
+
+#[test]
+fn test_render_svg_to_stdout() {
+    let output = bomberman()
+        .args(["render", "./tests/inputs/catedra_1.txt", "-", "0", "0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let svg = String::from_utf8(output.stdout).unwrap();
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("<polyline"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn test_render_png_golden_image() {
+    let path_output = "./tests/outputs/deviation_down.png";
+    let output = bomberman()
+        .args([
+            "render",
+            "./tests/inputs/deviation_down.txt",
+            path_output,
+            "1",
+            "0",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(compare_files(
+        path_output,
+        "./tests/expected_output/deviation_down.png"
+    ));
+}
+
+#[test]
+fn test_show_plain_when_not_a_terminal() {
+    let output = bomberman()
+        .args(["--show", "./tests/inputs/deviation_down.txt", "-", "1", "0"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let board = std::fs::read("./tests/expected_output/deviation_down.txt").unwrap();
+    let view = std::fs::read("./tests/expected_output/deviation_down_show.txt").unwrap();
+    assert_eq!(output.stdout, board);
+    assert_eq!(output.stderr, view);
+}
+
+#[test]
+fn test_run_command_with_named_flags() {
+    let output = bomberman()
+        .args([
+            "run",
+            "--input",
+            "./tests/inputs/deviation_down.txt",
+            "--output",
+            "-",
+            "--column",
+            "1",
+            "--row",
+            "0",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let board = std::fs::read("./tests/expected_output/deviation_down.txt").unwrap();
+    assert_eq!(output.stdout, board);
+}
+
+#[test]
+fn test_command_help_and_unknown_flag() {
+    let output = bomberman().args(["solve", "--help"]).output().unwrap();
+    assert!(output.status.success());
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(help.starts_with("Uso: bomberman solve tablero.txt\n"));
+
+    let output = bomberman()
+        .args(["check", "--colour", "./tests/inputs/catedra_1.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(output.stderr, b"Error: Opcion desconocida '--colour'\n");
+}
+
+#[test]
+fn test_generate_then_run() {
+    let generated = bomberman()
+        .args(["generate", "--seed", "7", "--size", "5"])
+        .output()
+        .unwrap();
+    assert!(generated.status.success());
+    assert!(generated.stdout.starts_with(b"# seed: 7\n# detonate: "));
+    let again = bomberman()
+        .args(["generate", "--seed", "7", "--size", "5"])
+        .output()
+        .unwrap();
+    assert_eq!(generated.stdout, again.stdout);
+
+    let output = run_binary_with_stdin(&["run", "-", "-"], &generated.stdout);
+    assert!(output.status.success());
+    assert_eq!(output.stdout.iter().filter(|b| **b == b'\n').count(), 5);
+}
+
+#[test]
+fn test_generate_rejects_invalid_values() {
+    for (args, flag) in [
+        (["--size", "5000000"], "--size"),
+        (["--size", "0"], "--size"),
+        (["--size", "1"], "--size"),
+        (["--seed", "abc"], "--seed"),
+        (["--bombs", "abc"], "--bombs"),
+    ] {
+        let output = bomberman().arg("generate").args(args).output().unwrap();
+        assert_eq!(output.status.code(), Some(2));
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            format!("Error: Valor invalido para la opcion '{}'\n", flag)
+        );
+    }
+}
+
+#[test]
+fn test_solve_ranks_bombs() {
+    let output = bomberman()
+        .args(["solve", "./tests/inputs/catedra_3.txt"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report = String::from_utf8(output.stdout).unwrap();
+    assert!(report.ends_with("La bomba en (0, 2) derrota a todos los enemigos\n"));
+}
+
+#[test]
+fn test_explain_rejects_cell_outside_board() {
+    let output = bomberman()
+        .args(["explain", "./tests/inputs/catedra_1.txt", "99", "99"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("Error: Las coordenadas deben ser enteros"));
+}
+
+#[test]
+fn test_batch_summary_and_junit_report() {
+    let path_output = "./tests/outputs/batch_junit.xml";
+    let output = bomberman()
+        .args([
+            "batch",
+            "./tests/inputs",
+            "--manifest",
+            "./tests/inputs/batch_cases.txt",
+            "--junit",
+            path_output,
+            "--jobs",
+            "3",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "fallo: deviation_down.txt (1, 0) (linea 5)\n\
+         error: unrecognized_item.txt (0, 0) (linea 6)\n\
+         5 caso(s): 3 ok, 1 fallo, 1 error, 0 sin salida esperada\n"
+    );
+    assert!(compare_files(
+        path_output,
+        "./tests/expected_output/batch_junit.xml"
+    ));
+}
+
+#[test]
+fn test_output_directory_created_and_overwrite_needs_force() {
+    let dir = std::env::temp_dir().join(format!("bomberman_output_{}", std::process::id()));
+    let output_dir = dir.join("resultados").to_string_lossy().into_owned();
+    let run = |extra: &[&str]| {
+        bomberman()
+            .args(["./tests/inputs/deviation_down.txt", &output_dir, "1", "0"])
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+
+    assert!(run(&[]).status.success());
+    let path_output = dir.join("resultados/deviation_down.txt");
+    assert!(compare_files(
+        &path_output.to_string_lossy(),
+        "./tests/expected_output/deviation_down.txt"
+    ));
+
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(6));
+    assert_eq!(
+        output.stderr,
+        b"Error: El archivo output ya existe, use --force para sobrescribirlo\n"
+    );
+    assert!(run(&["--force"]).status.success());
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_diff_report_overwrite_needs_force() {
+    let dir = std::env::temp_dir().join(format!("bomberman_diff_{}", std::process::id()));
+    let output_dir = dir.to_string_lossy().into_owned();
+    let report = dir.join("deviation_down.txt.diff");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&report, "previo\n").unwrap();
+    let run = |extra: &[&str]| {
+        bomberman()
+            .args([
+                "diff",
+                "./tests/inputs/deviation_down.txt",
+                &output_dir,
+                "1",
+                "0",
+            ])
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(6));
+    assert_eq!(std::fs::read_to_string(&report).unwrap(), "previo\n");
+    assert!(!dir.join("deviation_down.txt").exists());
+    assert!(run(&["--force"]).status.success());
+    assert_ne!(std::fs::read_to_string(&report).unwrap(), "previo\n");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_exit_code_and_json_diagnostic() {
+    let dir = std::env::temp_dir().join(format!("bomberman_exit_{}", std::process::id()));
+    let output_dir = dir.to_string_lossy().into_owned();
+    let output = bomberman()
+        .args([
+            "--error-format",
+            "json",
+            "./tests/inputs/no_bomb_at_coordinates.txt",
+            &output_dir,
+            "0",
+            "0",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "{\"error\":{\"code\":\"invalid_bomb_coordinate\",\"message\":\"En la coordenada proporcionada no se encontro una bomba\"}}\n"
+    );
+    assert!(compare_files(
+        &dir.join("no_bomb_at_coordinates.txt").to_string_lossy(),
+        "./tests/expected_output/no_bomb_at_coordinates.txt"
+    ));
+    std::fs::remove_dir_all(dir).unwrap();
+
+    let output = run_binary_with_stdin(&["-", "-", "0", "0"], b"B1 Z\n_ W\n");
+    assert_eq!(output.status.code(), Some(4));
+}
+
+#[test]
+fn test_messages_in_english() {
+    let output = bomberman()
+        .args(["--lang", "en", "check", "./tests/inputs/no_existe.txt"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(output.stderr, b"Error: The input file does not exist\n");
+
+    let output = bomberman()
+        .env("LANG", "en_US.UTF-8")
+        .args(["solve", "--help"])
+        .output()
+        .unwrap();
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(help.starts_with("Usage: bomberman solve tablero.txt\n"));
+    assert!(help.contains("--help  Shows this help\n"));
+
+    let output = bomberman()
+        .env("LANG", "en_US.UTF-8")
+        .args([
+            "--lang=es",
+            "--error-format",
+            "json",
+            "check",
+            "no_existe.txt",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.stderr,
+        b"{\"error\":{\"code\":\"input_path_error\",\"message\":\"El archivo input no existe\"}}\n"
+    );
+
+    let output = bomberman()
+        .args(["--lang", "fr", "check"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_trace_lines() {
+    let output = bomberman()
+        .args([
+            "./tests/inputs/deviation_down.txt",
+            "-",
+            "1",
+            "0",
+            "--trace",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let trace = String::from_utf8(output.stderr).unwrap();
+    assert!(trace
+        .starts_with("{\"event\":\"bomb_detonated\",\"row\":0,\"column\":1,\"item\":\"B2\"}\n"));
+    assert!(trace.contains(
+        "{\"event\":\"deflected\",\"row\":0,\"column\":2,\"from\":\"R\",\"to\":\"D\"}\n"
+    ));
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("R _ DD\n"));
+
+    let output = bomberman()
+        .args([
+            "./tests/inputs/deviation_down.txt",
+            "-",
+            "1",
+            "0",
+            "--trace",
+            "xml",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_watch_shows_first_frame() {
+    let mut child = bomberman()
+        .args([
+            "watch",
+            "./tests/inputs/deviation_down.txt",
+            "1",
+            "0",
+            "--interval",
+            "50",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    child.kill().unwrap();
+    let output = child.wait_with_output().unwrap();
+    let screen = String::from_utf8(output.stdout).unwrap();
+    assert!(screen.starts_with("Observando ./tests/inputs/deviation_down.txt"));
+    assert_eq!(screen.matches("Cadena: 1 (1, 0)\n").count(), 1);
+
+    let output = bomberman()
+        .args(["watch", "./tests/inputs/deviation_down.txt", "1"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_settings_file_defaults_and_flag_priority() {
+    let dir = std::env::temp_dir().join(format!("bomberman_settings_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("./tests/inputs/catedra_2.txt", dir.join("catedra_2.txt")).unwrap();
+    std::fs::write(
+        dir.join("bomberman.toml"),
+        "# proyecto\noutput = \"salida\"\ncoordinates = \"row-column\"\nrules = [\"destroy-rocks\"]\nlang = \"en\"\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| bomberman().current_dir(&dir).args(args).output().unwrap();
+
+    assert!(run(&["catedra_2.txt", "4", "2"]).status.success());
+    let board = std::fs::read_to_string(dir.join("salida/catedra_2.txt")).unwrap();
+    assert!(board.starts_with("_ _ _ _ _ _ _\n_ W _ W _ W _\n_ _ _ _ F1 _ _\n"));
+
+    let output = run(&["catedra_2.txt", "4", "2"]);
+    assert_eq!(
+        output.stderr,
+        b"Error: The output file already exists, use --force to overwrite it\n"
+    );
+    let output = run(&[
+        "--lang",
+        "es",
+        "catedra_2.txt",
+        "-",
+        "--column",
+        "2",
+        "--row",
+        "4",
+    ]);
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("_ _ _ _ _ _ _\n"));
+
+    std::fs::write(
+        dir.join("bomberman.toml"),
+        "output = \"salida\"\ncolour = \"red\"\n",
+    )
+    .unwrap();
+    let output = run(&["--error-format", "json", "catedra_2.txt", "4", "2"]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("{\"error\":{\"code\":\"invalid_settings\""));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_repl_runs_commands_on_one_game() {
+    let path = std::env::temp_dir().join(format!("bomberman_repl_{}.txt", std::process::id()));
+    let input = format!(
+        "detonate 0 0\nundo\nundo\nset 4 0 F3\nbogus\nsave {}\nquit\nshow\n",
+        path.display()
+    );
+    let output = run_binary_with_stdin(&["repl", "./tests/inputs/catedra_1.txt"], input.as_bytes());
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr.lines().collect::<Vec<_>>(),
+        [
+            "Error: No hay cambios para deshacer",
+            "Error: Comando no reconocido, use: show, detonate x y, undo, set x y item, rank, save ruta, load ruta, help o quit",
+        ]
+    );
+    let saved = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(saved.starts_with("B2 R R _ F3 _ _\n"));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with(&format!("Tablero guardado en {}\n", path.display())));
+}